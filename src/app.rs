@@ -1,21 +1,26 @@
 use std::fmt;
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 
-use iced::time;
-use iced::widget::{button, center, column, container, row, text, Column};
-use iced::{clipboard, Center, Element, Fill, Subscription, Task, Theme};
+use clap::ValueEnum;
+use iced::widget::{button, column, container, pick_list, row, text, vertical_space, Column};
+use iced::{clipboard, time, window, Element, Fill, Size, Subscription, Task, Theme};
 
 use jsonrpsee::core::ClientError;
-use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use jsonrpsee::http_client::HttpClient;
+use spaced::config::ExtendedNetwork;
 use spaced::rpc::{
     BidParams, OpenParams, RegisterParams, RpcClient, RpcWalletRequest, RpcWalletTxBuilder,
-    SendCoinsParams, ServerInfo,
+    SendCoinsParams, ServerInfo, TransferSpacesParams,
 };
 
+use crate::auth::{self, RpcAuth};
+use crate::config::Config;
 use crate::icon;
 use crate::screen;
 use crate::store::*;
+use crate::unit::Unit;
 
 #[derive(Debug, Clone)]
 enum RpcError {
@@ -55,26 +60,58 @@ enum RpcRequest {
     GetSpaceInfo {
         slabel: SLabel,
     },
+    ListWallets,
     LoadWallet {
         wallet: String,
     },
+    CreateWallet {
+        wallet: String,
+    },
+    ExportWallet {
+        wallet: String,
+    },
+    ImportWallet {
+        wallet: String,
+        export: String,
+    },
     GetBalance,
     GetWalletSpaces,
-    GetTransactions,
+    GetTransactions {
+        skip: usize,
+    },
+    ExportTransactions {
+        format: screen::transactions::ExportFormat,
+    },
+    ListUnspent,
     GetAddress {
         address_kind: AddressKind,
     },
     SendCoins {
-        recipient: String,
-        amount: Amount,
+        recipients: Vec<(String, Amount)>,
+        fee_rate: Option<FeeRate>,
     },
-    BidSpace {
-        slabel: SLabel,
-        amount: Amount,
-        open: bool,
+    BidSpaces {
+        bids: Vec<(SLabel, Amount, bool)>,
+        fee_rate: Option<FeeRate>,
+        from_basket: bool,
     },
     RegisterSpace {
         slabel: SLabel,
+        recipient: Option<String>,
+        fee_rate: Option<FeeRate>,
+    },
+    TransferSpace {
+        slabel: SLabel,
+        recipient: String,
+        fee_rate: Option<FeeRate>,
+    },
+    RenewSpace {
+        slabel: SLabel,
+        fee_rate: Option<FeeRate>,
+    },
+    BumpFee {
+        txid: Txid,
+        fee_rate: FeeRate,
     },
 }
 
@@ -87,22 +124,47 @@ enum RpcResponse {
         slabel: SLabel,
         result: RpcResult<Option<FullSpaceOut>>,
     },
+    ListWallets {
+        result: RpcResult<Vec<String>>,
+    },
     LoadWallet {
         wallet: String,
         result: RpcResult<()>,
     },
+    CreateWallet {
+        wallet: String,
+        result: RpcResult<()>,
+    },
+    ExportWallet {
+        wallet: String,
+        result: RpcResult<String>,
+    },
+    ImportWallet {
+        wallet: String,
+        result: RpcResult<()>,
+    },
     GetBalance {
         wallet: String,
         result: RpcResult<Balance>,
     },
     GetTransactions {
         wallet: String,
+        skip: usize,
         result: RpcResult<Vec<TxInfo>>,
     },
+    ExportTransactions {
+        wallet: String,
+        format: screen::transactions::ExportFormat,
+        result: RpcResult<String>,
+    },
     GetWalletSpaces {
         wallet: String,
         result: RpcResult<Vec<WalletOutput>>,
     },
+    ListUnspent {
+        wallet: String,
+        result: RpcResult<Vec<WalletOutput>>,
+    },
     GetAddress {
         wallet: String,
         address_kind: AddressKind,
@@ -111,21 +173,62 @@ enum RpcResponse {
     SendCoins {
         result: RpcResult<()>,
     },
-    BidSpace {
+    BidSpaces {
+        from_basket: bool,
         result: RpcResult<()>,
     },
     RegisterSpace {
         result: RpcResult<()>,
     },
+    TransferSpace {
+        result: RpcResult<()>,
+    },
+    RenewSpace {
+        result: RpcResult<()>,
+    },
+    BumpFee {
+        result: RpcResult<()>,
+    },
 }
 
 #[derive(Debug, Clone)]
 enum Screen {
+    Import,
     Home,
     Send,
     Receive,
     Space(String),
     Transactions,
+    Transaction(Txid),
+    Backup,
+    Settings,
+}
+
+impl Screen {
+    fn name(&self) -> Option<&'static str> {
+        match self {
+            Screen::Home => Some("home"),
+            Screen::Send => Some("send"),
+            Screen::Receive => Some("receive"),
+            Screen::Transactions => Some("transactions"),
+            Screen::Import
+            | Screen::Space(_)
+            | Screen::Transaction(_)
+            | Screen::Backup
+            | Screen::Settings => None,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [
+            Screen::Home,
+            Screen::Send,
+            Screen::Receive,
+            Screen::Transactions,
+        ]
+        .into_iter()
+        .find(|screen| screen.name() == Some(name))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -133,25 +236,50 @@ enum Message {
     RpcRequest(RpcRequest),
     RpcResponse(RpcResponse),
     SetScreen(Screen),
+    SetWallet(String),
+    SetUnit(Unit),
+    SetTheme(Theme),
+    WindowResized(Size),
+    WindowCloseRequested(window::Id),
+    ScreenSetup(screen::setup::Message),
+    ScreenImport(screen::import::Message),
     ScreenHome(screen::home::Message),
     ScreenSend(screen::send::Message),
     ScreenReceive(screen::receive::Message),
     ScreenSpace(screen::space::Message),
     ScreenTransactions(screen::transactions::Message),
+    ScreenTransaction(screen::transaction::Message),
+    ScreenBackup(screen::backup::Message),
+    ScreenSettings(screen::settings::Message),
 }
 
 pub struct App {
-    rpc_client: Arc<HttpClient>,
+    rpc_client: Option<Arc<HttpClient>>,
     rpc_error: Option<String>,
+    rpc_auth: RpcAuth,
+    rpc_credentials: Option<String>,
     store: Store,
+    config: Config,
+    config_error: Option<String>,
+    unit: Unit,
+    theme: Theme,
+    chain: ExtendedNetwork,
+    spaced_rpc_url: String,
+    network: Network,
     screen: Screen,
+    screen_setup: screen::setup::State,
+    screen_import: screen::import::State,
     screen_send: screen::send::State,
     screen_receive: screen::receive::State,
     screen_space: screen::space::State,
+    screen_transactions: screen::transactions::State,
+    screen_transaction: screen::transaction::State,
+    screen_backup: screen::backup::State,
+    screen_settings: screen::settings::State,
 }
 
 impl App {
-    pub fn run(args: crate::Args) -> iced::Result {
+    pub fn run(args: crate::Args, config: Config) -> iced::Result {
         let icon =
             iced::window::icon::from_rgba(include_bytes!("../assets/spaces.rgba").to_vec(), 64, 64)
                 .expect("Failed to load icon");
@@ -159,34 +287,72 @@ impl App {
         iced::application(Self::title, Self::update, Self::view)
             .font(icons_font)
             .subscription(Self::subscription)
+            .theme(Self::theme)
             .window(iced::window::Settings {
-                size: (1000.0, 500.0).into(),
+                size: config.window_size.unwrap_or((1000.0, 500.0)).into(),
                 min_size: Some((1000.0, 500.0).into()),
                 icon: Some(icon),
+                exit_on_close_request: false,
                 ..Default::default()
             })
-            .run_with(move || Self::new(args))
+            .run_with(move || Self::new(args, config))
     }
 
-    fn new(args: crate::Args) -> (Self, Task<Message>) {
-        let rpc_client: Arc<HttpClient> = Arc::new(
-            HttpClientBuilder::default()
-                .build(args.spaced_rpc_url.unwrap())
-                .unwrap(),
-        );
+    fn new(args: crate::Args, config: Config) -> (Self, Task<Message>) {
+        let chain = args.chain.unwrap();
+        let spaced_rpc_url = args.spaced_rpc_url.unwrap();
+        let wallet = args.wallet.unwrap();
+        let rpc_auth = RpcAuth::new(args.rpc_user, args.rpc_password, args.rpc_cookie);
+        let mut screen_setup = screen::setup::State::new(wallet.clone());
+        let (rpc_client, rpc_credentials) = match rpc_auth.credentials().and_then(|credentials| {
+            auth::build_client(&spaced_rpc_url, credentials.as_deref())
+                .map(|rpc_client| (rpc_client, credentials))
+        }) {
+            Ok((rpc_client, rpc_credentials)) => (Some(Arc::new(rpc_client)), rpc_credentials),
+            Err(e) => {
+                screen_setup.set_error(e);
+                (None, None)
+            }
+        };
+        let task = if rpc_client.is_some() {
+            Task::batch([
+                Task::done(Message::RpcRequest(RpcRequest::ListWallets)),
+                Task::done(Message::RpcRequest(RpcRequest::LoadWallet { wallet })),
+            ])
+        } else {
+            Task::none()
+        };
         (
             Self {
-                rpc_client,
                 rpc_error: None,
-                store: Default::default(),
-                screen: Screen::Home,
+                rpc_auth,
+                rpc_credentials,
+                store: Store::new(Self::labels_dir(chain)),
+                unit: config.unit,
+                theme: config.theme(),
+                network: chain.fallback_network(),
+                screen: config
+                    .last_screen
+                    .as_deref()
+                    .and_then(Screen::from_name)
+                    .filter(|_| rpc_client.is_some())
+                    .unwrap_or(Screen::Home),
+                rpc_client,
+                config,
+                config_error: None,
+                chain,
+                spaced_rpc_url,
+                screen_setup,
+                screen_import: Default::default(),
                 screen_send: Default::default(),
                 screen_receive: Default::default(),
                 screen_space: Default::default(),
+                screen_transactions: Default::default(),
+                screen_transaction: Default::default(),
+                screen_backup: Default::default(),
+                screen_settings: Default::default(),
             },
-            Task::done(Message::RpcRequest(RpcRequest::LoadWallet {
-                wallet: args.wallet.into(),
-            })),
+            task,
         )
     }
 
@@ -194,10 +360,100 @@ impl App {
         "Spaces Wallet".into()
     }
 
+    fn theme(&self) -> Theme {
+        self.theme.clone()
+    }
+
+    fn labels_dir(chain: ExtendedNetwork) -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| {
+            dir.join("spaces-wallet")
+                .join(format!("{:?}", chain).to_lowercase())
+                .join("labels")
+        })
+    }
+
+    fn connect(
+        &mut self,
+        chain: ExtendedNetwork,
+        spaced_rpc_url: String,
+        rpc_client: Arc<HttpClient>,
+        rpc_credentials: Option<String>,
+        wallet: String,
+    ) -> Task<Message> {
+        self.rpc_client = Some(rpc_client);
+        self.rpc_credentials = rpc_credentials;
+        self.rpc_error = None;
+        self.store = Store::new(Self::labels_dir(chain));
+        self.chain = chain;
+        self.spaced_rpc_url = spaced_rpc_url;
+        self.network = chain.fallback_network();
+        self.screen = Screen::Home;
+        self.screen_setup = screen::setup::State::new(wallet.clone());
+        self.screen_import = Default::default();
+        self.screen_send = Default::default();
+        self.screen_receive = Default::default();
+        self.screen_space = Default::default();
+        self.screen_transactions = Default::default();
+        self.screen_transaction = Default::default();
+        self.screen_backup = Default::default();
+        self.screen_settings = Default::default();
+        Task::batch([
+            Task::done(Message::RpcRequest(RpcRequest::ListWallets)),
+            Task::done(Message::RpcRequest(RpcRequest::LoadWallet { wallet })),
+        ])
+    }
+
+    // spaced writes a new cookie on every restart
+    fn refresh_rpc_cookie(&mut self) {
+        if !self.rpc_auth.is_cookie() || self.rpc_client.is_none() {
+            return;
+        }
+        match self.rpc_auth.credentials() {
+            Ok(credentials) if credentials != self.rpc_credentials => {
+                match auth::build_client(&self.spaced_rpc_url, credentials.as_deref()) {
+                    Ok(rpc_client) => {
+                        self.rpc_client = Some(Arc::new(rpc_client));
+                        self.rpc_credentials = credentials;
+                    }
+                    Err(e) => self.rpc_error = Some(e),
+                }
+            }
+            Ok(_) => {}
+            Err(e) => self.rpc_error = Some(e),
+        }
+    }
+
+    fn save_config(&mut self) {
+        if self.config.load_error.is_none() {
+            self.config_error = self.config.save().err();
+        }
+    }
+
+    fn set_wallet(&mut self, wallet: String) {
+        if self.store.get_wallet_name().as_ref() != Some(&wallet) {
+            self.screen_send = Default::default();
+            self.screen_space = Default::default();
+        }
+        self.store.set_wallet(wallet);
+    }
+
+    fn chains() -> Vec<String> {
+        ExtendedNetwork::value_variants()
+            .iter()
+            .filter_map(|chain| chain.to_possible_value())
+            .map(|value| value.get_name().to_string())
+            .collect()
+    }
+
     fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::RpcRequest(request) => {
-                let client = self.rpc_client.clone();
+                self.refresh_rpc_cookie();
+                let Some(client) = self.rpc_client.clone() else {
+                    self.screen_setup
+                        .set_error("Not connected, fix the RPC credentials in Settings".into());
+                    return Task::none();
+                };
                 match request {
                     RpcRequest::GetServerInfo => Task::perform(
                         async move {
@@ -217,6 +473,13 @@ impl App {
                         },
                         Message::RpcResponse,
                     ),
+                    RpcRequest::ListWallets => Task::perform(
+                        async move {
+                            let result = client.list_wallets().await.map_err(RpcError::from);
+                            RpcResponse::ListWallets { result }
+                        },
+                        Message::RpcResponse,
+                    ),
                     RpcRequest::LoadWallet { wallet } => Task::perform(
                         async move {
                             let result = client.wallet_load(&wallet).await.map_err(RpcError::from);
@@ -224,6 +487,49 @@ impl App {
                         },
                         Message::RpcResponse,
                     ),
+                    RpcRequest::CreateWallet { wallet } => Task::perform(
+                        async move {
+                            let result = client
+                                .wallet_create(&wallet)
+                                .await
+                                .map(|_| ())
+                                .map_err(RpcError::from);
+                            RpcResponse::CreateWallet { wallet, result }
+                        },
+                        Message::RpcResponse,
+                    ),
+                    RpcRequest::ExportWallet { wallet } => Task::perform(
+                        async move {
+                            let result = client
+                                .wallet_export(&wallet)
+                                .await
+                                .map_err(RpcError::from)
+                                .and_then(|export| {
+                                    serde_json::to_string_pretty(&export).map_err(|e| {
+                                        RpcError::Global {
+                                            message: e.to_string(),
+                                        }
+                                    })
+                                });
+                            RpcResponse::ExportWallet { wallet, result }
+                        },
+                        Message::RpcResponse,
+                    ),
+                    RpcRequest::ImportWallet { wallet, export } => Task::perform(
+                        async move {
+                            let result = match serde_json::from_str::<WalletExport>(&export) {
+                                Ok(export) => {
+                                    client.wallet_import(export).await.map_err(RpcError::from)
+                                }
+                                Err(e) => Err(RpcError::Call {
+                                    code: -1,
+                                    message: format!("Invalid wallet export: {}", e),
+                                }),
+                            };
+                            RpcResponse::ImportWallet { wallet, result }
+                        },
+                        Message::RpcResponse,
+                    ),
                     RpcRequest::GetBalance => {
                         if let Some(wallet) = self.store.get_wallet_name() {
                             Task::perform(
@@ -256,15 +562,80 @@ impl App {
                             Task::none()
                         }
                     }
-                    RpcRequest::GetTransactions => {
+                    RpcRequest::GetTransactions { skip } => {
                         if let Some(wallet) = self.store.get_wallet_name() {
                             Task::perform(
                                 async move {
                                     let result = client
-                                        .wallet_list_transactions(&wallet, 100, 0)
+                                        .wallet_list_transactions(
+                                            &wallet,
+                                            TRANSACTIONS_PAGE_SIZE,
+                                            skip,
+                                        )
                                         .await
                                         .map_err(RpcError::from);
-                                    RpcResponse::GetTransactions { wallet, result }
+                                    RpcResponse::GetTransactions {
+                                        wallet,
+                                        skip,
+                                        result,
+                                    }
+                                },
+                                Message::RpcResponse,
+                            )
+                        } else {
+                            Task::none()
+                        }
+                    }
+                    RpcRequest::ExportTransactions { format } => {
+                        if let Some(wallet) = self.store.get_wallet_name() {
+                            let labels = self
+                                .store
+                                .get_wallet()
+                                .map(|wallet| wallet.labels.clone())
+                                .unwrap_or_default();
+                            Task::perform(
+                                async move {
+                                    let mut transactions = Vec::new();
+                                    let result = loop {
+                                        match client
+                                            .wallet_list_transactions(
+                                                &wallet,
+                                                TRANSACTIONS_PAGE_SIZE,
+                                                transactions.len(),
+                                            )
+                                            .await
+                                        {
+                                            Ok(page) => {
+                                                let is_last = page.len() < TRANSACTIONS_PAGE_SIZE;
+                                                transactions.extend(page);
+                                                if is_last {
+                                                    break Ok(format.export(&transactions, &labels));
+                                                }
+                                            }
+                                            Err(e) => break Err(RpcError::from(e)),
+                                        }
+                                    };
+                                    RpcResponse::ExportTransactions {
+                                        wallet,
+                                        format,
+                                        result,
+                                    }
+                                },
+                                Message::RpcResponse,
+                            )
+                        } else {
+                            Task::none()
+                        }
+                    }
+                    RpcRequest::ListUnspent => {
+                        if let Some(wallet) = self.store.get_wallet_name() {
+                            Task::perform(
+                                async move {
+                                    let result = client
+                                        .wallet_list_unspent(&wallet)
+                                        .await
+                                        .map_err(RpcError::from);
+                                    RpcResponse::ListUnspent { wallet, result }
                                 },
                                 Message::RpcResponse,
                             )
@@ -292,7 +663,10 @@ impl App {
                             Task::none()
                         }
                     }
-                    RpcRequest::SendCoins { recipient, amount } => {
+                    RpcRequest::SendCoins {
+                        recipients,
+                        fee_rate,
+                    } => {
                         if let Some(wallet) = self.store.get_wallet_name() {
                             Task::perform(
                                 async move {
@@ -301,13 +675,15 @@ impl App {
                                             &wallet,
                                             RpcWalletTxBuilder {
                                                 bidouts: None,
-                                                requests: vec![RpcWalletRequest::SendCoins(
-                                                    SendCoinsParams {
-                                                        amount,
-                                                        to: recipient,
-                                                    },
-                                                )],
-                                                fee_rate: None,
+                                                requests: recipients
+                                                    .into_iter()
+                                                    .map(|(to, amount)| {
+                                                        RpcWalletRequest::SendCoins(
+                                                            SendCoinsParams { amount, to },
+                                                        )
+                                                    })
+                                                    .collect(),
+                                                fee_rate,
                                                 dust: None,
                                                 force: false,
                                                 confirmed_only: false,
@@ -325,33 +701,38 @@ impl App {
                             Task::none()
                         }
                     }
-                    RpcRequest::BidSpace {
-                        slabel,
-                        amount,
-                        open,
+                    RpcRequest::BidSpaces {
+                        bids,
+                        fee_rate,
+                        from_basket,
                     } => {
                         if let Some(wallet) = self.store.get_wallet_name() {
                             Task::perform(
                                 async move {
-                                    let name = slabel.to_string();
-                                    let amount = amount.to_sat();
                                     let result = client
                                         .wallet_send_request(
                                             &wallet,
                                             RpcWalletTxBuilder {
                                                 bidouts: None,
-                                                requests: vec![if open {
-                                                    RpcWalletRequest::Open(OpenParams {
-                                                        name,
-                                                        amount,
-                                                    })
-                                                } else {
-                                                    RpcWalletRequest::Bid(BidParams {
-                                                        name,
-                                                        amount,
+                                                requests: bids
+                                                    .into_iter()
+                                                    .map(|(slabel, amount, open)| {
+                                                        let name = slabel.to_string();
+                                                        let amount = amount.to_sat();
+                                                        if open {
+                                                            RpcWalletRequest::Open(OpenParams {
+                                                                name,
+                                                                amount,
+                                                            })
+                                                        } else {
+                                                            RpcWalletRequest::Bid(BidParams {
+                                                                name,
+                                                                amount,
+                                                            })
+                                                        }
                                                     })
-                                                }],
-                                                fee_rate: None,
+                                                    .collect(),
+                                                fee_rate,
                                                 dust: None,
                                                 force: false,
                                                 confirmed_only: false,
@@ -361,7 +742,10 @@ impl App {
                                         .await
                                         .map(|_| ())
                                         .map_err(RpcError::from);
-                                    RpcResponse::BidSpace { result }
+                                    RpcResponse::BidSpaces {
+                                        from_basket,
+                                        result,
+                                    }
                                 },
                                 Message::RpcResponse,
                             )
@@ -369,7 +753,11 @@ impl App {
                             Task::none()
                         }
                     }
-                    RpcRequest::RegisterSpace { slabel } => {
+                    RpcRequest::RegisterSpace {
+                        slabel,
+                        recipient,
+                        fee_rate,
+                    } => {
                         if let Some(wallet) = self.store.get_wallet_name() {
                             Task::perform(
                                 async move {
@@ -381,10 +769,10 @@ impl App {
                                                 requests: vec![RpcWalletRequest::Register(
                                                     RegisterParams {
                                                         name: slabel.to_string(),
-                                                        to: None,
+                                                        to: recipient,
                                                     },
                                                 )],
-                                                fee_rate: None,
+                                                fee_rate,
                                                 dust: None,
                                                 force: false,
                                                 confirmed_only: false,
@@ -402,6 +790,93 @@ impl App {
                             Task::none()
                         }
                     }
+                    RpcRequest::TransferSpace {
+                        slabel,
+                        recipient,
+                        fee_rate,
+                    } => {
+                        if let Some(wallet) = self.store.get_wallet_name() {
+                            Task::perform(
+                                async move {
+                                    let result = client
+                                        .wallet_send_request(
+                                            &wallet,
+                                            RpcWalletTxBuilder {
+                                                bidouts: None,
+                                                requests: vec![RpcWalletRequest::Transfer(
+                                                    TransferSpacesParams {
+                                                        spaces: vec![slabel.to_string()],
+                                                        to: Some(recipient),
+                                                    },
+                                                )],
+                                                fee_rate,
+                                                dust: None,
+                                                force: false,
+                                                confirmed_only: false,
+                                                skip_tx_check: false,
+                                            },
+                                        )
+                                        .await
+                                        .map(|_| ())
+                                        .map_err(RpcError::from);
+                                    RpcResponse::TransferSpace { result }
+                                },
+                                Message::RpcResponse,
+                            )
+                        } else {
+                            Task::none()
+                        }
+                    }
+                    RpcRequest::RenewSpace { slabel, fee_rate } => {
+                        if let Some(wallet) = self.store.get_wallet_name() {
+                            Task::perform(
+                                async move {
+                                    let result = client
+                                        .wallet_send_request(
+                                            &wallet,
+                                            RpcWalletTxBuilder {
+                                                bidouts: None,
+                                                requests: vec![RpcWalletRequest::Transfer(
+                                                    TransferSpacesParams {
+                                                        spaces: vec![slabel.to_string()],
+                                                        to: None,
+                                                    },
+                                                )],
+                                                fee_rate,
+                                                dust: None,
+                                                force: false,
+                                                confirmed_only: false,
+                                                skip_tx_check: false,
+                                            },
+                                        )
+                                        .await
+                                        .map(|_| ())
+                                        .map_err(RpcError::from);
+                                    RpcResponse::RenewSpace { result }
+                                },
+                                Message::RpcResponse,
+                            )
+                        } else {
+                            Task::none()
+                        }
+                    }
+                    RpcRequest::BumpFee { txid, fee_rate } => {
+                        if let Some(wallet) = self.store.get_wallet_name() {
+                            Task::perform(
+                                async move {
+                                    let result = client
+                                        .wallet_bump_fee(&wallet, txid, fee_rate, false)
+                                        .await
+                                        .map(|_| ())
+                                        .map_err(RpcError::from);
+                                    RpcResponse::BumpFee { result }
+                                },
+                                Message::RpcResponse,
+                            )
+                        } else {
+                            Task::none()
+                        }
+                    }
                 }
             }
             Message::RpcResponse(response) => {
@@ -421,6 +896,16 @@ impl App {
                     RpcResponse::GetSpaceInfo { slabel, result } => {
                         match result {
                             Ok(out) => {
+                                match out.as_ref() {
+                                    Some(out) => {
+                                        self.store
+                                            .space_outpoints
+                                            .insert(slabel.clone(), out.outpoint());
+                                    }
+                                    None => {
+                                        self.store.space_outpoints.remove(&slabel);
+                                    }
+                                }
                                 self.store.spaces.insert(
                                     slabel,
                                     out.map(|out| out.spaceout.space.unwrap().covenant),
@@ -432,10 +917,90 @@ impl App {
                         }
                         Task::none()
                     }
+                    RpcResponse::ListWallets { result } => {
+                        match result {
+                            Ok(wallet_names) => {
+                                self.store.wallet_names = wallet_names;
+                                if let Some(wallet) = self.store.get_wallet_name() {
+                                    if !self.store.wallet_names.contains(&wallet) {
+                                        self.store.wallet_names.push(wallet);
+                                    }
+                                }
+                            }
+                            Err(e) => {
+                                self.rpc_error = Some(e.to_string());
+                            }
+                        }
+                        Task::none()
+                    }
                     RpcResponse::LoadWallet { wallet, result } => match result {
                         Ok(_) => {
-                            self.store.wallet = Some(Wallet::new(wallet));
-                            Task::done(Message::SetScreen(Screen::Home))
+                            self.set_wallet(wallet);
+                            if matches!(self.screen, Screen::Import) {
+                                Task::done(Message::SetScreen(Screen::Home))
+                            } else {
+                                Task::done(Message::SetScreen(self.screen.clone()))
+                            }
+                        }
+                        Err(RpcError::Call { message, .. }) => {
+                            if matches!(self.screen, Screen::Import) {
+                                self.screen_import.set_error(message);
+                            } else if self.store.get_wallet().is_some() {
+                                self.rpc_error = Some(message);
+                            } else {
+                                self.screen_setup.set_error(message);
+                            }
+                            Task::none()
+                        }
+                        Err(e) => {
+                            self.screen_setup.set_error(e.to_string());
+                            self.rpc_error = Some(e.to_string());
+                            Task::none()
+                        }
+                    },
+                    RpcResponse::CreateWallet { wallet, result } => match result {
+                        Ok(_) => {
+                            Task::done(Message::RpcRequest(RpcRequest::ExportWallet { wallet }))
+                        }
+                        Err(RpcError::Call { message, .. }) => {
+                            self.screen_setup.set_error(message);
+                            Task::none()
+                        }
+                        Err(e) => {
+                            self.screen_setup.set_error(e.to_string());
+                            self.rpc_error = Some(e.to_string());
+                            Task::none()
+                        }
+                    },
+                    RpcResponse::ExportWallet { wallet, result } => {
+                        let is_loaded = self.store.get_wallet_with_name(&wallet).is_some();
+                        match result {
+                            Ok(export) => {
+                                if is_loaded {
+                                    self.screen_backup.set_export(export);
+                                } else {
+                                    self.screen_setup.set_recovery(export);
+                                }
+                            }
+                            Err(e) => {
+                                if is_loaded {
+                                    self.screen_backup.set_error(e.to_string());
+                                } else {
+                                    self.screen_setup.set_error(e.to_string());
+                                }
+                            }
+                        }
+                        Task::none()
+                    }
+                    RpcResponse::ImportWallet { wallet, result } => match result {
+                        Ok(_) => Task::done(Message::RpcRequest(RpcRequest::LoadWallet { wallet })),
+                        Err(RpcError::Call { code, message }) => {
+                            if code == -1 {
+                                self.screen_import.set_error(message);
+                            } else {
+                                self.rpc_error = Some(message);
+                            }
+                            Task::none()
                         }
                         Err(e) => {
                             self.rpc_error = Some(e.to_string());
@@ -478,14 +1043,87 @@ impl App {
                         }
                         Task::none()
                     }
-                    RpcResponse::GetTransactions { wallet, result } => {
+                    RpcResponse::GetTransactions {
+                        wallet,
+                        skip,
+                        result,
+                    } => {
+                        if skip != 0 {
+                            self.screen_transactions.set_loaded_more();
+                        }
                         match result {
                             Ok(transactions) => {
                                 if let Some(wallet) = self.store.get_wallet_with_name(&wallet) {
-                                    wallet.transactions = transactions;
+                                    wallet.add_transactions(skip, transactions);
+                                }
+                            }
+                            Err(e) => {
+                                self.rpc_error = Some(e.to_string());
+                            }
+                        }
+                        Task::none()
+                    }
+                    RpcResponse::ExportTransactions {
+                        wallet,
+                        format,
+                        result,
+                    } => match result {
+                        Ok(content) => {
+                            let file_name =
+                                format!("{}-transactions.{}", wallet, format.extension());
+                            Task::perform(
+                                async move {
+                                    let file = rfd::AsyncFileDialog::new()
+                                        .set_file_name(file_name)
+                                        .add_filter(
+                                            format.extension().to_uppercase(),
+                                            &[format.extension()],
+                                        )
+                                        .save_file()
+                                        .await?;
+                                    Some(
+                                        file.write(content.as_bytes())
+                                            .await
+                                            .map(|_| file.path().display().to_string())
+                                            .map_err(|e| e.to_string()),
+                                    )
+                                },
+                                |result| {
+                                    Message::ScreenTransactions(
+                                        screen::transactions::Message::ExportSaved(result),
+                                    )
+                                },
+                            )
+                        }
+                        Err(RpcError::Call { code, message }) => {
+                            if code == -1 {
+                                self.screen_transactions.set_error(message);
+                            } else {
+                                self.screen_transactions.set_export_failed();
+                                self.rpc_error = Some(message);
+                            }
+                            Task::none()
+                        }
+                        Err(e) => {
+                            self.screen_transactions.set_export_failed();
+                            self.rpc_error = Some(e.to_string());
+                            Task::none()
+                        }
+                    },
+                    RpcResponse::ListUnspent { wallet, result } => {
+                        match result {
+                            Ok(outputs) => {
+                                if self.store.get_wallet_name().as_ref() == Some(&wallet) {
+                                    let coins: Vec<Amount> = outputs
+                                        .into_iter()
+                                        .filter(|out| out.space.is_none())
+                                        .map(|out| out.output.txout.value)
+                                        .collect();
+                                    self.screen_send.set_max_amount(&coins, self.unit);
                                 }
                             }
                             Err(e) => {
+                                self.screen_send.cancel_max();
                                 self.rpc_error = Some(e.to_string());
                             }
                         }
@@ -505,6 +1143,12 @@ impl App {
                                         AddressKind::Space => wallet.space_address = Some(address),
                                     }
                                 }
+                                self.screen_receive.update_request(
+                                    self.store
+                                        .get_wallet()
+                                        .and_then(|wallet| wallet.coin_address.as_ref()),
+                                    self.unit,
+                                );
                             }
                             Err(e) => {
                                 self.rpc_error = Some(e.to_string());
@@ -527,8 +1171,16 @@ impl App {
                             Task::none()
                         }
                     },
-                    RpcResponse::BidSpace { result } => match result {
-                        Ok(_) => Task::done(Message::SetScreen(Screen::Transactions)),
+                    RpcResponse::BidSpaces {
+                        from_basket,
+                        result,
+                    } => match result {
+                        Ok(_) => {
+                            if from_basket {
+                                self.screen_space.clear_basket();
+                            }
+                            Task::done(Message::SetScreen(Screen::Transactions))
+                        }
                         Err(RpcError::Call { code, message }) => {
                             if code == -1 {
                                 self.screen_space.set_error(message);
@@ -557,12 +1209,73 @@ impl App {
                             Task::none()
                         }
                     },
+                    RpcResponse::TransferSpace { result } => match result {
+                        Ok(_) => Task::done(Message::SetScreen(Screen::Transactions)),
+                        Err(RpcError::Call { code, message }) => {
+                            if code == -1 {
+                                self.screen_space.set_error(message);
+                            } else {
+                                self.rpc_error = Some(message);
+                            }
+                            Task::none()
+                        }
+                        Err(e) => {
+                            self.rpc_error = Some(e.to_string());
+                            Task::none()
+                        }
+                    },
+                    RpcResponse::RenewSpace { result } => match result {
+                        Ok(_) => Task::done(Message::SetScreen(Screen::Transactions)),
+                        Err(RpcError::Call { code, message }) => {
+                            if code == -1 {
+                                self.screen_space.set_error(message);
+                            } else {
+                                self.rpc_error = Some(message);
+                            }
+                            Task::none()
+                        }
+                        Err(e) => {
+                            self.rpc_error = Some(e.to_string());
+                            Task::none()
+                        }
+                    },
+                    RpcResponse::BumpFee { result } => match result {
+                        Ok(_) => {
+                            self.screen_transactions.reset_bump_fee();
+                            Task::done(Message::RpcRequest(RpcRequest::GetTransactions { skip: 0 }))
+                        }
+                        Err(RpcError::Call { code, message }) => {
+                            if code == -1 {
+                                self.screen_transactions.set_error(message);
+                            } else {
+                                self.rpc_error = Some(message);
+                            }
+                            Task::none()
+                        }
+                        Err(e) => {
+                            self.rpc_error = Some(e.to_string());
+                            Task::none()
+                        }
+                    },
                 }
             }
             Message::SetScreen(screen) => {
+                if let Screen::Space(ref space_name) = screen {
+                    if !matches!(self.screen, Screen::Space(ref current) if current == space_name) {
+                        self.screen_space.reset_space();
+                    }
+                }
                 self.screen = screen;
+                if let Some(name) = self.screen.name() {
+                    if self.config.last_screen.as_deref() != Some(name) {
+                        self.config.last_screen = Some(name.to_string());
+                        self.save_config();
+                    }
+                }
                 match self.screen {
+                    Screen::Import => Task::none(),
                     Screen::Home => Task::batch([
+                        Task::done(Message::RpcRequest(RpcRequest::GetServerInfo)),
                         Task::done(Message::RpcRequest(RpcRequest::GetBalance)),
                         Task::done(Message::RpcRequest(RpcRequest::GetWalletSpaces)),
                     ]),
@@ -585,34 +1298,227 @@ impl App {
                         }
                     }
                     Screen::Transactions => {
-                        Task::done(Message::RpcRequest(RpcRequest::GetTransactions))
+                        Task::done(Message::RpcRequest(RpcRequest::GetTransactions { skip: 0 }))
+                    }
+                    Screen::Transaction(txid) => {
+                        self.screen_transaction = screen::transaction::State::new(
+                            self.store
+                                .get_wallet()
+                                .and_then(|wallet| wallet.labels.transaction(&txid)),
+                        );
+                        Task::done(Message::RpcRequest(RpcRequest::GetServerInfo))
+                    }
+                    Screen::Backup => {
+                        self.screen_backup.reset();
+                        if let Some(wallet) = self.store.get_wallet_name() {
+                            Task::done(Message::RpcRequest(RpcRequest::ExportWallet { wallet }))
+                        } else {
+                            Task::none()
+                        }
+                    }
+                    Screen::Settings => {
+                        let saved = self.screen_settings.is_saved();
+                        self.screen_settings = screen::settings::State::new(
+                            self.chain
+                                .to_possible_value()
+                                .map(|value| value.get_name().to_string())
+                                .unwrap_or_default(),
+                            self.spaced_rpc_url.clone(),
+                            self.store.get_wallet_name().unwrap_or_default(),
+                            &self.rpc_auth,
+                        );
+                        if saved {
+                            self.screen_settings.set_saved();
+                        }
+                        Task::none()
                     }
                 }
             }
+            Message::SetWallet(wallet) => {
+                if self.store.get_wallet_with_name(&wallet).is_some() {
+                    self.set_wallet(wallet);
+                    Task::done(Message::SetScreen(self.screen.clone()))
+                } else {
+                    Task::done(Message::RpcRequest(RpcRequest::LoadWallet { wallet }))
+                }
+            }
+            Message::SetUnit(unit) => {
+                self.screen_send.set_unit(self.unit, unit);
+                self.screen_receive.set_unit(self.unit, unit);
+                self.screen_space.set_unit(self.unit, unit);
+                self.unit = unit;
+                self.screen_receive.update_request(
+                    self.store
+                        .get_wallet()
+                        .and_then(|wallet| wallet.coin_address.as_ref()),
+                    self.unit,
+                );
+                self.config.unit = unit;
+                self.save_config();
+                Task::none()
+            }
+            Message::SetTheme(theme) => {
+                self.config.set_theme(&theme);
+                self.theme = theme;
+                self.save_config();
+                Task::none()
+            }
+            Message::WindowResized(size) => {
+                self.config.window_size = Some((size.width, size.height));
+                Task::none()
+            }
+            Message::WindowCloseRequested(id) => {
+                if self.config_error.is_none() {
+                    self.save_config();
+                    if let Some(error) = self.config_error.take() {
+                        self.config_error =
+                            Some(format!("{}, close again to quit without saving", error));
+                        return Task::none();
+                    }
+                }
+                window::close(id)
+            }
+            Message::ScreenSettings(message) => {
+                match screen::settings::update(&mut self.screen_settings, message) {
+                    screen::settings::Task::SetTheme(theme) => Task::done(Message::SetTheme(theme)),
+                    screen::settings::Task::SetUnit(unit) => Task::done(Message::SetUnit(unit)),
+                    screen::settings::Task::SaveConnection {
+                        chain,
+                        spaced_rpc_url,
+                        wallet,
+                        rpc_auth,
+                    } => {
+                        let Ok(network) = ExtendedNetwork::from_str(&chain, true) else {
+                            self.screen_settings
+                                .set_error(format!("Unknown chain {}", chain));
+                            return Task::none();
+                        };
+                        let (rpc_client, rpc_credentials) =
+                            match rpc_auth.credentials().and_then(|credentials| {
+                                auth::build_client(&spaced_rpc_url, credentials.as_deref())
+                                    .map(|rpc_client| (rpc_client, credentials))
+                            }) {
+                                Ok(result) => result,
+                                Err(e) => {
+                                    self.screen_settings.set_error(e);
+                                    return Task::none();
+                                }
+                            };
+                        self.config.chain = Some(chain);
+                        self.config.spaced_rpc_url = Some(spaced_rpc_url.clone());
+                        self.config.wallet = Some(wallet.clone());
+                        self.config.set_rpc_auth(&rpc_auth);
+                        if let Err(e) = self.config.save() {
+                            self.screen_settings.set_error(e);
+                            return Task::none();
+                        }
+                        self.rpc_auth = rpc_auth;
+                        let task = self.connect(
+                            network,
+                            spaced_rpc_url,
+                            Arc::new(rpc_client),
+                            rpc_credentials,
+                            wallet,
+                        );
+                        self.screen_settings.set_saved();
+                        task
+                    }
+                    screen::settings::Task::Back => Task::done(Message::SetScreen(Screen::Home)),
+                    screen::settings::Task::None => Task::none(),
+                }
+            }
+            Message::ScreenSetup(message) => {
+                match screen::setup::update(&mut self.screen_setup, message) {
+                    screen::setup::Task::CreateWallet { wallet } => {
+                        Task::done(Message::RpcRequest(RpcRequest::CreateWallet { wallet }))
+                    }
+                    screen::setup::Task::LoadWallet { wallet } => {
+                        Task::done(Message::RpcRequest(RpcRequest::LoadWallet { wallet }))
+                    }
+                    screen::setup::Task::Import => Task::done(Message::SetScreen(Screen::Import)),
+                    screen::setup::Task::Settings => {
+                        Task::done(Message::SetScreen(Screen::Settings))
+                    }
+                    screen::setup::Task::WriteClipboard(s) => clipboard::write(s),
+                    screen::setup::Task::None => Task::none(),
+                }
+            }
+            Message::ScreenImport(message) => {
+                match screen::import::update(&mut self.screen_import, message) {
+                    screen::import::Task::PickFile => Task::future(async {
+                        let file = rfd::AsyncFileDialog::new()
+                            .add_filter("JSON", &["json"])
+                            .pick_file()
+                            .await?;
+                        Some(
+                            String::from_utf8(file.read().await)
+                                .map_err(|_| "File is not a valid wallet export".to_string()),
+                        )
+                    })
+                    .and_then(|result| {
+                        Task::done(Message::ScreenImport(screen::import::Message::FileLoaded(
+                            result,
+                        )))
+                    }),
+                    screen::import::Task::ImportWallet { wallet, export } => {
+                        Task::done(Message::RpcRequest(RpcRequest::ImportWallet {
+                            wallet,
+                            export,
+                        }))
+                    }
+                    screen::import::Task::Back => Task::done(Message::SetScreen(Screen::Home)),
+                    screen::import::Task::None => Task::none(),
+                }
+            }
             Message::ScreenHome(message) => match message {
                 screen::home::Message::SpaceClicked { space_name } => {
                     Task::done(Message::SetScreen(Screen::Space(space_name)))
                 }
             },
             Message::ScreenSend(message) => {
-                match screen::send::update(&mut self.screen_send, message) {
-                    screen::send::Task::SendCoins { recipient, amount } => {
-                        Task::done(Message::RpcRequest(RpcRequest::SendCoins {
-                            recipient,
-                            amount,
-                        }))
+                match screen::send::update(&mut self.screen_send, message, self.unit, self.network)
+                {
+                    screen::send::Task::ListUnspent => {
+                        Task::done(Message::RpcRequest(RpcRequest::ListUnspent))
+                    }
+                    screen::send::Task::ResolveSpace { slabel } => {
+                        Task::done(Message::RpcRequest(RpcRequest::GetSpaceInfo { slabel }))
                     }
+                    screen::send::Task::SendCoins {
+                        recipients,
+                        fee_rate,
+                    } => Task::done(Message::RpcRequest(RpcRequest::SendCoins {
+                        recipients,
+                        fee_rate,
+                    })),
                     screen::send::Task::None => Task::none(),
                 }
             }
             Message::ScreenReceive(message) => {
-                match screen::receive::update(&mut self.screen_receive, message) {
+                match screen::receive::update(
+                    &mut self.screen_receive,
+                    message,
+                    self.unit,
+                    self.store
+                        .get_wallet()
+                        .and_then(|wallet| wallet.coin_address.as_ref()),
+                ) {
                     screen::receive::Task::WriteClipboard(s) => clipboard::write(s),
+                    screen::receive::Task::SetAddressLabel { address, label } => {
+                        if let Some(wallet_name) = self.store.get_wallet_name() {
+                            if let Some(wallet) = self.store.get_wallet_with_name(&wallet_name) {
+                                if let Err(e) = wallet.labels.set_address(address, label) {
+                                    self.screen_receive.set_error(e);
+                                }
+                            }
+                        }
+                        Task::none()
+                    }
                     screen::receive::Task::None => Task::none(),
                 }
             }
             Message::ScreenSpace(message) => {
-                match screen::space::update(&mut self.screen_space, message) {
+                match screen::space::update(&mut self.screen_space, message, self.unit) {
                     screen::space::Task::SetSpace { space_name } => {
                         Task::done(Message::SetScreen(Screen::Space(space_name)))
                     }
@@ -620,87 +1526,265 @@ impl App {
                         slabel,
                         amount,
                         open,
-                    } => Task::done(Message::RpcRequest(RpcRequest::BidSpace {
+                        fee_rate,
+                    } => Task::done(Message::RpcRequest(RpcRequest::BidSpaces {
+                        bids: vec![(slabel, amount, open)],
+                        fee_rate,
+                        from_basket: false,
+                    })),
+                    screen::space::Task::BidSpaces { bids, fee_rate } => {
+                        Task::done(Message::RpcRequest(RpcRequest::BidSpaces {
+                            bids,
+                            fee_rate,
+                            from_basket: true,
+                        }))
+                    }
+                    screen::space::Task::RegisterSpace {
                         slabel,
-                        amount,
-                        open,
+                        recipient,
+                        fee_rate,
+                    } => Task::done(Message::RpcRequest(RpcRequest::RegisterSpace {
+                        slabel,
+                        recipient,
+                        fee_rate,
+                    })),
+                    screen::space::Task::TransferSpace {
+                        slabel,
+                        recipient,
+                        fee_rate,
+                    } => Task::done(Message::RpcRequest(RpcRequest::TransferSpace {
+                        slabel,
+                        recipient,
+                        fee_rate,
                     })),
-                    screen::space::Task::RegisterSpace { slabel } => {
-                        Task::done(Message::RpcRequest(RpcRequest::RegisterSpace { slabel }))
+                    screen::space::Task::RenewSpace { slabel, fee_rate } => {
+                        Task::done(Message::RpcRequest(RpcRequest::RenewSpace {
+                            slabel,
+                            fee_rate,
+                        }))
                     }
                     screen::space::Task::None => Task::none(),
                 }
             }
-            Message::ScreenTransactions(message) => match message {
-                screen::transactions::Message::TxidCopyPress { txid } => clipboard::write(txid),
-            },
+            Message::ScreenTransactions(message) => {
+                match screen::transactions::update(&mut self.screen_transactions, message) {
+                    screen::transactions::Task::WriteClipboard(s) => clipboard::write(s),
+                    screen::transactions::Task::BumpFee { txid, fee_rate } => {
+                        Task::done(Message::RpcRequest(RpcRequest::BumpFee { txid, fee_rate }))
+                    }
+                    screen::transactions::Task::ShowTransaction { txid } => {
+                        Task::done(Message::SetScreen(Screen::Transaction(txid)))
+                    }
+                    screen::transactions::Task::Export(format) => {
+                        Task::done(Message::RpcRequest(RpcRequest::ExportTransactions {
+                            format,
+                        }))
+                    }
+                    screen::transactions::Task::LoadMore => {
+                        Task::done(Message::RpcRequest(RpcRequest::GetTransactions {
+                            skip: self
+                                .store
+                                .get_wallet()
+                                .map_or(0, |wallet| wallet.transactions.len()),
+                        }))
+                    }
+                    screen::transactions::Task::None => Task::none(),
+                }
+            }
+            Message::ScreenTransaction(message) => {
+                match screen::transaction::update(&mut self.screen_transaction, message) {
+                    screen::transaction::Task::Back => {
+                        Task::done(Message::SetScreen(Screen::Transactions))
+                    }
+                    screen::transaction::Task::WriteClipboard(s) => clipboard::write(s),
+                    screen::transaction::Task::SetLabel(label) => {
+                        if let (Screen::Transaction(txid), Some(wallet_name)) =
+                            (&self.screen, self.store.get_wallet_name())
+                        {
+                            if let Some(wallet) = self.store.get_wallet_with_name(&wallet_name) {
+                                if let Err(e) = wallet.labels.set_transaction(*txid, label) {
+                                    self.screen_transaction.set_error(e);
+                                }
+                            }
+                        }
+                        Task::none()
+                    }
+                    screen::transaction::Task::None => Task::none(),
+                }
+            }
+            Message::ScreenBackup(message) => {
+                match screen::backup::update(&mut self.screen_backup, message) {
+                    screen::backup::Task::WriteClipboard(s) => clipboard::write(s),
+                    screen::backup::Task::SaveFile(export) => {
+                        let file_name =
+                            format!("{}.json", self.store.get_wallet_name().unwrap_or_default());
+                        Task::future(async move {
+                            let file = rfd::AsyncFileDialog::new()
+                                .set_file_name(file_name)
+                                .add_filter("JSON", &["json"])
+                                .save_file()
+                                .await?;
+                            Some(
+                                file.write(export.as_bytes())
+                                    .await
+                                    .map(|_| file.path().display().to_string())
+                                    .map_err(|e| e.to_string()),
+                            )
+                        })
+                        .and_then(|result| {
+                            Task::done(Message::ScreenBackup(screen::backup::Message::FileSaved(
+                                result,
+                            )))
+                        })
+                    }
+                    screen::backup::Task::None => Task::none(),
+                }
+            }
         }
     }
 
     fn view(&self) -> Element<Message> {
-        let main: Element<Message> = if self.store.wallet.is_some() {
-            row![
-                navbar(&self.screen),
-                container(match self.screen {
-                    Screen::Home => screen::home::view(
-                        self.store.wallet.as_ref().unwrap().balance,
-                        self.store.get_wallet_spaces().unwrap(),
-                    )
-                    .map(Message::ScreenHome),
-                    Screen::Send => screen::send::view(&self.screen_send).map(Message::ScreenSend),
-                    Screen::Receive => screen::receive::view(
-                        &self.screen_receive,
-                        self.store.wallet.as_ref().unwrap().coin_address.as_ref(),
-                        self.store.wallet.as_ref().unwrap().space_address.as_ref(),
-                    )
-                    .map(Message::ScreenReceive),
-                    Screen::Space(ref space_name) => {
-                        screen::space::view(
-                            &self.screen_space,
-                            self.store.tip_height,
-                            space_name,
-                            match SLabel::from_str(&format!("@{}", space_name)) {
-                                Ok(slabel) => Some((
-                                    slabel.clone(),
+        let main: Element<Message> = if let Some(wallet) = self.store.get_wallet() {
+            let screen: Element<Message> = match self.screen {
+                Screen::Import => {
+                    screen::import::view(&self.screen_import).map(Message::ScreenImport)
+                }
+                Screen::Home => screen::home::view(
+                    self.unit,
+                    self.store.tip_height,
+                    wallet.balance,
+                    self.store.get_wallet_spaces().unwrap(),
+                )
+                .map(Message::ScreenHome),
+                Screen::Send => screen::send::view(
+                    &self.screen_send,
+                    self.unit,
+                    self.network,
+                    self.store.tip_height,
+                    wallet.balance,
+                    self.screen_send
+                        .recipient_slabels()
+                        .map(|slabel| {
+                            slabel.map(|slabel| {
+                                (
                                     self.store.spaces.get(&slabel),
-                                    self.store.wallet.as_ref().unwrap().spaces.contains(&slabel),
-                                )),
-                                Err(_) => None,
-                            },
-                        )
-                        .map(Message::ScreenSpace)
-                    }
-                    Screen::Transactions => screen::transactions::view(
-                        &self.store.wallet.as_ref().unwrap().transactions
-                    )
-                    .map(Message::ScreenTransactions),
-                })
-                .style(|theme: &Theme| {
+                                    self.store.space_outpoints.get(&slabel),
+                                )
+                            })
+                        })
+                        .collect(),
+                )
+                .map(Message::ScreenSend),
+                Screen::Receive => screen::receive::view(
+                    &self.screen_receive,
+                    self.unit,
+                    wallet.coin_address.as_ref(),
+                    wallet.space_address.as_ref(),
+                    &wallet.labels,
+                )
+                .map(Message::ScreenReceive),
+                Screen::Space(ref space_name) => screen::space::view(
+                    &self.screen_space,
+                    self.unit,
+                    self.store.tip_height,
+                    wallet.balance,
+                    space_name,
+                    match SLabel::from_str(&format!("@{}", space_name)) {
+                        Ok(slabel) => Some((
+                            slabel.clone(),
+                            self.store.spaces.get(&slabel),
+                            wallet.spaces.contains(&slabel),
+                        )),
+                        Err(_) => None,
+                    },
+                )
+                .map(Message::ScreenSpace),
+                Screen::Transactions => screen::transactions::view(
+                    &self.screen_transactions,
+                    self.unit,
+                    &wallet.transactions,
+                    wallet.has_more_transactions,
+                    &wallet.labels,
+                )
+                .map(Message::ScreenTransactions),
+                Screen::Transaction(txid) => screen::transaction::view(
+                    &self.screen_transaction,
+                    self.unit,
+                    self.store.tip_height,
+                    wallet.transactions.iter().find(|tx| tx.txid == txid),
+                    wallet.labels.transaction(&txid),
+                )
+                .map(Message::ScreenTransaction),
+                Screen::Backup => screen::backup::view(&self.screen_backup, &wallet.name)
+                    .map(Message::ScreenBackup),
+                Screen::Settings => screen::settings::view(
+                    &self.screen_settings,
+                    Self::chains(),
+                    self.theme.clone(),
+                    self.unit,
+                    true,
+                )
+                .map(Message::ScreenSettings),
+            };
+            row![
+                navbar(
+                    &self.screen,
+                    &self.store.wallet_names,
+                    &wallet.name,
+                    self.unit
+                ),
+                container(screen).style(|theme: &Theme| {
                     container::Style::default()
                         .background(theme.extended_palette().background.weak.color)
                 })
             ]
             .into()
+        } else if matches!(self.screen, Screen::Import) {
+            screen::import::view(&self.screen_import).map(Message::ScreenImport)
+        } else if matches!(self.screen, Screen::Settings) {
+            screen::settings::view(
+                &self.screen_settings,
+                Self::chains(),
+                self.theme.clone(),
+                self.unit,
+                false,
+            )
+            .map(Message::ScreenSettings)
         } else {
-            center(text("Loading").align_x(Center)).into()
+            screen::setup::view(&self.screen_setup).map(Message::ScreenSetup)
         };
         Column::new()
+            .push_maybe(self.config.load_error.as_ref().map(errorbar))
+            .push_maybe(self.config_error.as_ref().map(errorbar))
+            .push_maybe(
+                self.store
+                    .get_wallet()
+                    .and_then(|wallet| wallet.labels.load_error())
+                    .map(errorbar),
+            )
             .push_maybe(self.rpc_error.as_ref().map(errorbar))
             .push(main)
             .into()
     }
 
     fn subscription(&self) -> Subscription<Message> {
-        if self.store.wallet.is_some() && self.rpc_error.is_none() {
+        let refresh = if self.store.get_wallet().is_some() && self.rpc_error.is_none() {
             match self.screen {
-                Screen::Transactions => time::every(time::Duration::from_secs(5))
-                    .map(|_| Message::RpcRequest(RpcRequest::GetTransactions)),
+                Screen::Transactions | Screen::Transaction(_) => {
+                    time::every(time::Duration::from_secs(5))
+                        .map(|_| Message::RpcRequest(RpcRequest::GetTransactions { skip: 0 }))
+                }
                 _ => time::every(time::Duration::from_secs(5))
                     .map(|_| Message::RpcRequest(RpcRequest::GetServerInfo)),
             }
         } else {
             Subscription::none()
-        }
+        };
+        Subscription::batch([
+            refresh,
+            window::resize_events().map(|(_, size)| Message::WindowResized(size)),
+            window::close_requests().map(Message::WindowCloseRequested),
+        ])
     }
 }
 
@@ -721,7 +1805,12 @@ fn errorbar<'a>(error: &'a String) -> Element<'a, Message> {
     .into()
 }
 
-fn navbar<'a>(current_screen: &'a Screen) -> Element<'a, Message> {
+fn navbar<'a>(
+    current_screen: &'a Screen,
+    wallet_names: &'a [String],
+    wallet_name: &'a String,
+    unit: Unit,
+) -> Element<'a, Message> {
     let navbar_button = |label, icon: char, is_active, screen| {
         let button = button(row![text(icon).font(icon::FONT).size(18), text(label)].spacing(10))
             .style(move |theme, status| {
@@ -739,6 +1828,12 @@ fn navbar<'a>(current_screen: &'a Screen) -> Element<'a, Message> {
     };
 
     container(column![
+        container(
+            pick_list(wallet_names, Some(wallet_name), Message::SetWallet)
+                .padding(10)
+                .width(Fill)
+        )
+        .padding(5),
         navbar_button(
             "Home",
             icon::ARTBOARD,
@@ -766,9 +1861,37 @@ fn navbar<'a>(current_screen: &'a Screen) -> Element<'a, Message> {
         navbar_button(
             "Transactions",
             icon::ARROWS_EXCHANGE,
-            matches!(current_screen, Screen::Transactions),
+            matches!(
+                current_screen,
+                Screen::Transactions | Screen::Transaction(_)
+            ),
             Screen::Transactions
         ),
+        navbar_button(
+            "Backup",
+            icon::COPY,
+            matches!(current_screen, Screen::Backup),
+            Screen::Backup
+        ),
+        navbar_button(
+            "Import",
+            icon::ARROW_DOWN_TO_ARC,
+            matches!(current_screen, Screen::Import),
+            Screen::Import
+        ),
+        navbar_button(
+            "Settings",
+            icon::ARTBOARD,
+            matches!(current_screen, Screen::Settings),
+            Screen::Settings
+        ),
+        vertical_space(),
+        container(
+            pick_list(Unit::ALL, Some(unit), Message::SetUnit)
+                .padding(10)
+                .width(Fill)
+        )
+        .padding(5),
     ])
     .width(200)
     .into()
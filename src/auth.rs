@@ -0,0 +1,58 @@
+use std::fs;
+use std::path::PathBuf;
+
+use base64::prelude::*;
+use jsonrpsee::http_client::{HeaderMap, HeaderValue, HttpClient, HttpClientBuilder};
+
+#[derive(Debug, Clone, Default)]
+pub enum RpcAuth {
+    #[default]
+    None,
+    UserPass {
+        user: String,
+        password: String,
+    },
+    Cookie(PathBuf),
+}
+
+impl RpcAuth {
+    pub fn new(user: Option<String>, password: Option<String>, cookie: Option<PathBuf>) -> Self {
+        match (cookie, user) {
+            (Some(cookie), _) => Self::Cookie(cookie),
+            (None, Some(user)) => Self::UserPass {
+                user,
+                password: password.unwrap_or_default(),
+            },
+            (None, None) => Self::None,
+        }
+    }
+
+    pub fn is_cookie(&self) -> bool {
+        matches!(self, Self::Cookie(_))
+    }
+
+    pub fn credentials(&self) -> Result<Option<String>, String> {
+        match self {
+            Self::None => Ok(None),
+            Self::UserPass { user, password } => Ok(Some(format!("{}:{}", user, password))),
+            Self::Cookie(path) => fs::read_to_string(path)
+                .map(|cookie| Some(cookie.trim().to_string()))
+                .map_err(|e| format!("Failed to read RPC cookie {}: {}", path.display(), e)),
+        }
+    }
+}
+
+pub fn build_client(spaced_rpc_url: &str, credentials: Option<&str>) -> Result<HttpClient, String> {
+    let mut headers = HeaderMap::new();
+    if let Some(credentials) = credentials {
+        let mut value =
+            HeaderValue::from_str(&format!("Basic {}", BASE64_STANDARD.encode(credentials)))
+                .map_err(|e| e.to_string())?;
+        value.set_sensitive(true);
+        headers.insert("Authorization", value);
+    }
+    HttpClientBuilder::default()
+        .set_headers(headers)
+        .build(spaced_rpc_url)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,189 @@
+use std::fmt;
+
+use crate::store::{Amount, Denomination};
+
+const SCHEME: &str = "bitcoin:";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Uri {
+    pub address: String,
+    pub amount: Option<Amount>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+}
+
+impl Uri {
+    pub fn is_uri(s: &str) -> bool {
+        s.get(..SCHEME.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(SCHEME))
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        if !Self::is_uri(s) {
+            return None;
+        }
+        let s = &s[SCHEME.len()..];
+        let (address, query) = s.split_once('?').unwrap_or((s, ""));
+        if address.is_empty() {
+            return None;
+        }
+        let mut uri = Self {
+            address: address.to_string(),
+            ..Default::default()
+        };
+        for param in query.split('&').filter(|param| !param.is_empty()) {
+            let (key, value) = param.split_once('=').unwrap_or((param, ""));
+            let value = decode(value)?;
+            match key {
+                "amount" => {
+                    uri.amount = Some(Amount::from_str_in(&value, Denomination::Bitcoin).ok()?)
+                }
+                "label" => uri.label = Some(value),
+                "message" => uri.message = Some(value),
+                key if key.starts_with("req-") => return None,
+                _ => {}
+            }
+        }
+        Some(uri)
+    }
+}
+
+impl fmt::Display for Uri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", SCHEME, self.address)?;
+        let mut separator = '?';
+        let mut param = |f: &mut fmt::Formatter<'_>, key: &str, value: &str| {
+            let result = write!(f, "{}{}={}", separator, key, value);
+            separator = '&';
+            result
+        };
+        if let Some(amount) = self.amount {
+            param(f, "amount", &amount.to_string_in(Denomination::Bitcoin))?;
+        }
+        if let Some(label) = self.label.as_ref() {
+            param(f, "label", &encode(label))?;
+        }
+        if let Some(message) = self.message.as_ref() {
+            param(f, "message", &encode(message))?;
+        }
+        Ok(())
+    }
+}
+
+fn encode(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            result.push(byte as char);
+        } else {
+            result.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    result
+}
+
+fn decode(s: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut iter = s.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq";
+
+    #[test]
+    fn is_uri() {
+        assert!(Uri::is_uri("bitcoin:bc1q"));
+        assert!(Uri::is_uri("BITCOIN:bc1q"));
+        assert!(!Uri::is_uri("bitcoin"));
+        assert!(!Uri::is_uri("bc1qbitcoin:"));
+        assert!(!Uri::is_uri("bitcoi\u{20ac}..."));
+    }
+
+    #[test]
+    fn parse() {
+        let uri = Uri::parse(&format!(
+            "bitcoin:{}?amount=0.001&label=Luke-Jr&message=Donation&foo=bar",
+            ADDRESS
+        ))
+        .unwrap();
+        assert_eq!(
+            uri,
+            Uri {
+                address: ADDRESS.into(),
+                amount: Some(Amount::from_sat(100_000)),
+                label: Some("Luke-Jr".into()),
+                message: Some("Donation".into()),
+            }
+        );
+        assert_eq!(
+            Uri::parse(&format!("bitcoin:{}", ADDRESS)).unwrap(),
+            Uri {
+                address: ADDRESS.into(),
+                ..Default::default()
+            }
+        );
+        assert!(Uri::parse("bitcoin:").is_none());
+        assert!(Uri::parse("bitcoin:?amount=1").is_none());
+        assert!(Uri::parse(&format!("bitcoin:{}?amount=abc", ADDRESS)).is_none());
+    }
+
+    #[test]
+    fn parse_percent_decoding() {
+        let uri = Uri::parse(&format!(
+            "bitcoin:{}?label=Luke%20Jr&message=%E2%82%AC%26more",
+            ADDRESS
+        ))
+        .unwrap();
+        assert_eq!(uri.label.as_deref(), Some("Luke Jr"));
+        assert_eq!(uri.message.as_deref(), Some("\u{20ac}&more"));
+        assert!(Uri::parse(&format!("bitcoin:{}?label=%ZZ", ADDRESS)).is_none());
+        assert!(Uri::parse(&format!("bitcoin:{}?label=%2", ADDRESS)).is_none());
+        assert!(Uri::parse(&format!("bitcoin:{}?label=%FF", ADDRESS)).is_none());
+    }
+
+    #[test]
+    fn parse_rejects_required_params() {
+        assert!(Uri::parse(&format!("bitcoin:{}?req-somethingnew=1", ADDRESS)).is_none());
+        assert!(Uri::parse(&format!("bitcoin:{}?amount=1&req-pop=x", ADDRESS)).is_none());
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let uri = Uri {
+            address: ADDRESS.into(),
+            amount: Some(Amount::from_sat(123_456_789)),
+            label: Some("Bob & Co / 100% \u{20ac}".into()),
+            message: Some("a=b?c".into()),
+        };
+        let s = uri.to_string();
+        assert_eq!(
+            s,
+            format!(
+                "bitcoin:{}?amount=1.23456789&label=Bob%20%26%20Co%20%2F%20100%25%20%E2%82%AC\
+                 &message=a%3Db%3Fc",
+                ADDRESS
+            )
+        );
+        assert_eq!(Uri::parse(&s).unwrap(), uri);
+
+        let uri = Uri {
+            address: ADDRESS.into(),
+            label: Some("label".into()),
+            ..Default::default()
+        };
+        assert_eq!(uri.to_string(), format!("bitcoin:{}?label=label", ADDRESS));
+        assert_eq!(Uri::parse(&uri.to_string()).unwrap(), uri);
+    }
+}
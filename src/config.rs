@@ -0,0 +1,110 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use iced::Theme;
+use serde::{Deserialize, Serialize};
+use spaced::config::ExtendedNetwork;
+
+use crate::auth::RpcAuth;
+use crate::unit::Unit;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub chain: Option<String>,
+    pub spaced_rpc_url: Option<String>,
+    pub wallet: Option<String>,
+    pub rpc_user: Option<String>,
+    pub rpc_password: Option<String>,
+    pub rpc_cookie: Option<PathBuf>,
+    pub theme: Option<String>,
+    pub unit: Unit,
+    pub last_screen: Option<String>,
+    pub window_size: Option<(f32, f32)>,
+    #[serde(skip)]
+    pub load_error: Option<String>,
+}
+
+impl Config {
+    fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("spaces-wallet").join("config.toml"))
+    }
+
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Self::default(),
+            Err(e) => return Self::failed(&path, e.to_string()),
+        };
+        let config: Self = match toml::from_str(&content) {
+            Ok(config) => config,
+            Err(e) => return Self::failed(&path, e.to_string()),
+        };
+        match config.chain.as_ref() {
+            Some(chain) if config.chain().is_none() => {
+                Self::failed(&path, format!("unknown chain \"{}\"", chain))
+            }
+            _ => config,
+        }
+    }
+
+    fn failed(path: &Path, error: String) -> Self {
+        Self {
+            load_error: Some(format!(
+                "Failed to load config {}, changes will not be saved: {}",
+                path.display(),
+                error
+            )),
+            ..Default::default()
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        if let Some(error) = self.load_error.as_ref() {
+            return Err(error.clone());
+        }
+        let path = Self::path().ok_or("Config directory is not available")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let content = toml::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, content).map_err(|e| format!("Failed to save config: {}", e))
+    }
+
+    pub fn chain(&self) -> Option<ExtendedNetwork> {
+        self.chain
+            .as_ref()
+            .and_then(|chain| ExtendedNetwork::from_str(chain, true).ok())
+    }
+
+    pub fn set_rpc_auth(&mut self, rpc_auth: &RpcAuth) {
+        self.rpc_user = None;
+        self.rpc_password = None;
+        self.rpc_cookie = None;
+        match rpc_auth {
+            RpcAuth::None => {}
+            RpcAuth::UserPass { user, password } => {
+                self.rpc_user = Some(user.clone());
+                self.rpc_password = Some(password.clone()).filter(|password| !password.is_empty());
+            }
+            RpcAuth::Cookie(path) => self.rpc_cookie = Some(path.clone()),
+        }
+    }
+
+    pub fn theme(&self) -> Theme {
+        self.theme
+            .as_ref()
+            .and_then(|name| Theme::ALL.iter().find(|theme| theme.to_string() == *name))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn set_theme(&mut self, theme: &Theme) {
+        self.theme = Some(theme.to_string());
+    }
+}
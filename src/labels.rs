@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::store::Txid;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Labels {
+    #[serde(default)]
+    transactions: BTreeMap<Txid, String>,
+    #[serde(default)]
+    addresses: BTreeMap<String, String>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    #[serde(skip)]
+    load_error: Option<String>,
+}
+
+impl Labels {
+    pub fn load(path: PathBuf) -> Self {
+        let labels = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| e.to_string()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        };
+        match labels {
+            Ok(labels) => Self {
+                path: Some(path),
+                ..labels
+            },
+            Err(e) => Self {
+                load_error: Some(format!(
+                    "Failed to load labels {}, changes will not be saved: {}",
+                    path.display(),
+                    e
+                )),
+                path: Some(path),
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn load_error(&self) -> Option<&String> {
+        self.load_error.as_ref()
+    }
+
+    fn save(&self) -> Result<(), String> {
+        if let Some(error) = self.load_error.as_ref() {
+            return Err(error.clone());
+        }
+        let Some(path) = self.path.as_ref() else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self).unwrap())
+            .map_err(|e| format!("Failed to save labels: {}", e))
+    }
+
+    pub fn transaction(&self, txid: &Txid) -> Option<&str> {
+        self.transactions.get(txid).map(String::as_str)
+    }
+
+    pub fn set_transaction(&mut self, txid: Txid, label: String) -> Result<(), String> {
+        if label.is_empty() {
+            self.transactions.remove(&txid);
+        } else {
+            self.transactions.insert(txid, label);
+        }
+        self.save()
+    }
+
+    pub fn address(&self, address: &str) -> Option<&str> {
+        self.addresses.get(address).map(String::as_str)
+    }
+
+    pub fn set_address(&mut self, address: String, label: String) -> Result<(), String> {
+        if label.is_empty() {
+            self.addresses.remove(&address);
+        } else {
+            self.addresses.insert(address, label);
+        }
+        self.save()
+    }
+}
@@ -1,28 +1,47 @@
 mod app;
+mod auth;
+mod bip21;
+mod config;
+mod labels;
 mod screen;
 mod store;
+mod unit;
+mod widget;
 
 use app::App;
+use config::Config;
 
 mod icon {
     include!("../assets/icons.rs");
 }
 
-use clap::Parser;
+use std::path::PathBuf;
+
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
 use spaced::config::{default_spaces_rpc_port, ExtendedNetwork};
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 pub struct Args {
-    /// Bitcoin network to use
-    #[arg(long, env = "SPACED_CHAIN", default_value = "mainnet")]
-    chain: ExtendedNetwork,
+    /// Bitcoin network to use [default: mainnet]
+    #[arg(long, env = "SPACED_CHAIN")]
+    chain: Option<ExtendedNetwork>,
     /// Spaced RPC URL [default: based on specified chain]
     #[arg(long)]
     spaced_rpc_url: Option<String>,
-    /// Specify wallet to use
-    #[arg(long, short, global = true, default_value = "default")]
-    wallet: String,
+    /// Specify wallet to use [default: default]
+    #[arg(long, short, global = true)]
+    wallet: Option<String>,
+    /// Spaced RPC user for basic authentication
+    #[arg(long, env = "SPACED_RPC_USER")]
+    rpc_user: Option<String>,
+    /// Spaced RPC password for basic authentication
+    #[arg(long, env = "SPACED_RPC_PASSWORD")]
+    rpc_password: Option<String>,
+    /// Path to the spaced RPC cookie file
+    #[arg(long, conflicts_with = "rpc_user")]
+    rpc_cookie: Option<PathBuf>,
 }
 
 fn default_spaced_rpc_url(chain: &ExtendedNetwork) -> String {
@@ -31,9 +50,44 @@ fn default_spaced_rpc_url(chain: &ExtendedNetwork) -> String {
 
 pub fn main() -> iced::Result {
     let mut args = Args::parse();
+    let config = Config::load();
+    if args.chain.is_none() {
+        if let Some(error) = config.load_error.as_ref() {
+            Args::command()
+                .error(
+                    ErrorKind::InvalidValue,
+                    format!("{}, fix it or pass --chain", error),
+                )
+                .exit();
+        }
+        args.chain = Some(config.chain().unwrap_or(ExtendedNetwork::Mainnet));
+    }
     if args.spaced_rpc_url.is_none() {
-        args.spaced_rpc_url = Some(default_spaced_rpc_url(&args.chain));
+        args.spaced_rpc_url = Some(
+            config
+                .spaced_rpc_url
+                .clone()
+                .unwrap_or_else(|| default_spaced_rpc_url(args.chain.as_ref().unwrap())),
+        );
+    }
+    if args.wallet.is_none() {
+        args.wallet = Some(config.wallet.clone().unwrap_or_else(|| "default".into()));
+    }
+    if args.rpc_user.is_none() && args.rpc_cookie.is_none() {
+        args.rpc_user = config.rpc_user.clone();
+        args.rpc_cookie = config.rpc_cookie.clone();
+    }
+    if args.rpc_password.is_none() && args.rpc_cookie.is_none() {
+        args.rpc_password = config.rpc_password.clone();
+    }
+    if args.rpc_password.is_some() && args.rpc_user.is_none() {
+        Args::command()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "--rpc-password requires --rpc-user or rpc_user in the config file",
+            )
+            .exit();
     }
 
-    App::run(args)
+    App::run(args, config)
 }
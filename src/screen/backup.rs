@@ -0,0 +1,131 @@
+use iced::widget::{button, center, column, container, row, scrollable, text, Column};
+use iced::{Border, Center, Element, Fill, Font, Shrink, Theme};
+
+use crate::icon;
+
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    export: Option<String>,
+    saved_path: Option<String>,
+    error: Option<String>,
+}
+
+impl State {
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+
+    pub fn set_export(&mut self, export: String) {
+        self.export = Some(export)
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    CopyPress(String),
+    SavePress,
+    FileSaved(Result<String, String>),
+}
+
+#[derive(Debug, Clone)]
+pub enum Task {
+    None,
+    WriteClipboard(String),
+    SaveFile(String),
+}
+
+pub fn update(state: &mut State, message: Message) -> Task {
+    match message {
+        Message::CopyPress(s) => Task::WriteClipboard(s),
+        Message::SavePress => {
+            state.error = None;
+            state.saved_path = None;
+            match state.export.as_ref() {
+                Some(export) => Task::SaveFile(export.clone()),
+                None => Task::None,
+            }
+        }
+        Message::FileSaved(result) => {
+            match result {
+                Ok(path) => state.saved_path = Some(path),
+                Err(e) => state.error = Some(e),
+            }
+            Task::None
+        }
+    }
+}
+
+pub fn view<'a>(state: &'a State, wallet_name: &'a str) -> Element<'a, Message> {
+    let main: Element<'a, Message> = match state.export.as_ref() {
+        Some(export) => column![
+            text(format!(
+                "Backup of wallet \"{}\". Anyone with this data can spend your coins and spaces.",
+                wallet_name
+            )),
+            container(
+                row![
+                    scrollable(text(export).font(Font::MONOSPACE)).width(Fill),
+                    button(text(icon::COPY).font(icon::FONT))
+                        .style(button::secondary)
+                        .on_press(Message::CopyPress(export.clone())),
+                ]
+                .spacing(5),
+            )
+            .padding(10)
+            .max_height(200)
+            .style(|theme: &Theme| {
+                let palette = theme.extended_palette();
+                container::Style::default().border(Border {
+                    color: palette.secondary.base.text,
+                    width: 1.0,
+                    radius: 0.into(),
+                })
+            }),
+            container(
+                button("Save to file")
+                    .on_press(Message::SavePress)
+                    .padding([10, 20])
+                    .width(Shrink),
+            )
+            .align_x(Center)
+            .width(Fill),
+        ]
+        .push_maybe(
+            state
+                .saved_path
+                .as_ref()
+                .map(|path| text(format!("Saved to {}", path)).width(Fill).center()),
+        )
+        .spacing(10)
+        .into(),
+        None => center(text("Loading")).into(),
+    };
+
+    center(
+        Column::new()
+            .push_maybe(state.error.as_ref().map(|error| {
+                container(
+                    text(error)
+                        .style(|theme: &Theme| text::Style {
+                            color: Some(theme.extended_palette().danger.base.text),
+                        })
+                        .center()
+                        .width(Fill),
+                )
+                .style(|theme: &Theme| {
+                    container::Style::default()
+                        .background(theme.extended_palette().danger.base.color)
+                })
+                .width(Fill)
+                .padding([10, 30])
+            }))
+            .push(main)
+            .spacing(10),
+    )
+    .padding(20)
+    .into()
+}
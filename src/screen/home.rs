@@ -1,7 +1,9 @@
-use iced::widget::{button, column, scrollable, text, Column};
-use iced::{Element, Fill};
+use iced::widget::{button, column, horizontal_space, row, scrollable, text, Column};
+use iced::{Center, Element, Fill};
 
 use crate::store::{Amount, Covenant, SLabel};
+use crate::unit::Unit;
+use crate::widget::expiry;
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -9,21 +11,32 @@ pub enum Message {
 }
 
 pub fn view<'a>(
+    unit: Unit,
+    tip_height: u32,
     balance: Amount,
     spaces: impl Iterator<Item = (&'a SLabel, &'a Covenant)>,
 ) -> Element<'a, Message> {
     column![
-        text("Balance (SAT)"),
-        text(balance.to_sat()),
+        text("Balance"),
+        text(unit.format(balance)),
         text("Your spaces"),
-        scrollable(Column::with_children(spaces.map(|(slabel, _)| {
-            button(text(slabel.to_string()))
-                .on_press(Message::SpaceClicked {
-                    space_name: slabel.to_string()[1..].to_string(),
-                })
-                .width(Fill)
-                .padding([10, 20])
-                .into()
+        scrollable(Column::with_children(spaces.map(|(slabel, covenant)| {
+            button(
+                row![text(slabel.to_string()), horizontal_space()]
+                    .push_maybe(match covenant {
+                        Covenant::Transfer { expire_height, .. } => {
+                            Some(expiry::view(tip_height, *expire_height))
+                        }
+                        _ => None,
+                    })
+                    .align_y(Center),
+            )
+            .on_press(Message::SpaceClicked {
+                space_name: slabel.to_string()[1..].to_string(),
+            })
+            .width(Fill)
+            .padding([10, 20])
+            .into()
         })))
     ]
     .spacing(5)
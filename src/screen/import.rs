@@ -0,0 +1,139 @@
+use iced::widget::{button, center, column, container, row, text, text_editor, Column};
+use iced::{Center, Element, Fill, Font, Shrink, Theme};
+
+use crate::store::WalletExport;
+
+#[derive(Debug, Default)]
+pub struct State {
+    content: text_editor::Content,
+    error: Option<String>,
+}
+
+impl State {
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ContentAction(text_editor::Action),
+    PickFilePress,
+    FileLoaded(Result<String, String>),
+    ImportPress,
+    BackPress,
+}
+
+#[derive(Debug, Clone)]
+pub enum Task {
+    None,
+    PickFile,
+    ImportWallet { wallet: String, export: String },
+    Back,
+}
+
+fn validate(content: &text_editor::Content) -> Result<(String, String), String> {
+    let export = content.text();
+    let export = export.trim();
+    serde_json::from_str::<WalletExport>(export)
+        .map(|wallet| (wallet.label, export.to_string()))
+        .map_err(|e| format!("Invalid wallet export: {}", e))
+}
+
+pub fn update(state: &mut State, message: Message) -> Task {
+    match message {
+        Message::ContentAction(action) => {
+            state.error = None;
+            state.content.perform(action);
+            Task::None
+        }
+        Message::PickFilePress => Task::PickFile,
+        Message::FileLoaded(result) => {
+            match result {
+                Ok(content) => {
+                    state.error = None;
+                    state.content = text_editor::Content::with_text(&content);
+                }
+                Err(e) => state.error = Some(e),
+            }
+            Task::None
+        }
+        Message::ImportPress => match validate(&state.content) {
+            Ok((wallet, export)) => {
+                state.error = None;
+                Task::ImportWallet { wallet, export }
+            }
+            Err(e) => {
+                state.error = Some(e);
+                Task::None
+            }
+        },
+        Message::BackPress => {
+            state.error = None;
+            Task::Back
+        }
+    }
+}
+
+pub fn view<'a>(state: &'a State) -> Element<'a, Message> {
+    center(
+        Column::new()
+            .push_maybe(state.error.as_ref().map(|error| {
+                container(
+                    text(error)
+                        .style(|theme: &Theme| text::Style {
+                            color: Some(theme.extended_palette().danger.base.text),
+                        })
+                        .center()
+                        .width(Fill),
+                )
+                .style(|theme: &Theme| {
+                    container::Style::default()
+                        .background(theme.extended_palette().danger.base.color)
+                })
+                .width(Fill)
+                .padding([10, 30])
+            }))
+            .push(
+                column![
+                    text("Paste a wallet export or load it from a file"),
+                    text_editor(&state.content)
+                        .on_action(Message::ContentAction)
+                        .font(Font::MONOSPACE)
+                        .height(200)
+                        .padding(10),
+                ]
+                .spacing(5),
+            )
+            .push(
+                container(
+                    row![
+                        button("Back")
+                            .style(button::secondary)
+                            .on_press(Message::BackPress)
+                            .padding([10, 20])
+                            .width(Shrink),
+                        button("Open file")
+                            .style(button::secondary)
+                            .on_press(Message::PickFilePress)
+                            .padding([10, 20])
+                            .width(Shrink),
+                        button("Import")
+                            .on_press_maybe(
+                                (!state.content.text().trim().is_empty())
+                                    .then_some(Message::ImportPress),
+                            )
+                            .padding([10, 20])
+                            .width(Shrink),
+                    ]
+                    .spacing(10),
+                )
+                .align_x(Center)
+                .width(Fill),
+            )
+            .spacing(10)
+            .max_width(600),
+    )
+    .padding(20)
+    .into()
+}
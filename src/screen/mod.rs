@@ -1,5 +1,10 @@
+pub mod backup;
 pub mod home;
+pub mod import;
 pub mod receive;
 pub mod send;
+pub mod settings;
+pub mod setup;
 pub mod space;
+pub mod transaction;
 pub mod transactions;
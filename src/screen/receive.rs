@@ -1,17 +1,61 @@
-use iced::widget::{button, center, column, container, qr_code, row, text, toggler};
+use iced::widget::{
+    button, center, column, container, qr_code, row, text, text_input, toggler, Column, Row,
+};
 use iced::{Border, Center, Element, Fill, Font, Padding, Theme};
 
+use crate::bip21::Uri;
 use crate::icon;
+use crate::labels::Labels;
 use crate::store::Address;
+use crate::unit::Unit;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Default)]
 pub struct State {
     coin_address: bool,
+    amount: String,
+    label: String,
+    note: String,
+    request: Option<Address>,
+    error: Option<String>,
+}
+
+impl State {
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error)
+    }
+
+    pub fn set_unit(&mut self, from: Unit, to: Unit) {
+        self.amount = from.convert_input(&self.amount, to);
+    }
+
+    pub fn update_request(&mut self, coin_address: Option<&Address>, unit: Unit) {
+        self.request = None;
+        let Some(address) = coin_address.filter(|_| self.coin_address) else {
+            return;
+        };
+        let uri = Uri {
+            address: address.text.clone(),
+            amount: unit.parse(&self.amount),
+            label: Some(self.label.clone()).filter(|label| !label.is_empty()),
+            message: None,
+        };
+        if uri.amount.is_none() && uri.label.is_none() {
+            return;
+        }
+        match Address::try_new(uri.to_string()) {
+            Ok(request) => self.request = Some(request),
+            Err(_) => self.error = Some("Payment request is too long for a QR code".into()),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     AddressKindToggle(bool),
+    AmountInput(String),
+    LabelInput(String),
+    NoteInput(String),
+    NoteSavePress { address: String },
     CopyPress(String),
 }
 
@@ -19,28 +63,62 @@ pub enum Message {
 pub enum Task {
     None,
     WriteClipboard(String),
+    SetAddressLabel { address: String, label: String },
 }
 
-pub fn update(state: &mut State, message: Message) -> Task {
+pub fn update(
+    state: &mut State,
+    message: Message,
+    unit: Unit,
+    coin_address: Option<&Address>,
+) -> Task {
+    state.error = None;
     match message {
-        Message::AddressKindToggle(coin_address) => {
-            state.coin_address = coin_address;
+        Message::AddressKindToggle(coin_address_kind) => {
+            state.coin_address = coin_address_kind;
+            state.update_request(coin_address, unit);
+            Task::None
+        }
+        Message::AmountInput(amount) => {
+            if unit.is_valid_input(&amount) {
+                state.amount = amount;
+                state.update_request(coin_address, unit);
+            }
+            Task::None
+        }
+        Message::LabelInput(label) => {
+            state.label = label;
+            state.update_request(coin_address, unit);
             Task::None
         }
+        Message::NoteInput(note) => {
+            state.note = note;
+            Task::None
+        }
+        Message::NoteSavePress { address } => Task::SetAddressLabel {
+            address,
+            label: state.note.trim().to_string(),
+        },
         Message::CopyPress(s) => Task::WriteClipboard(s),
     }
 }
 
 pub fn view<'a>(
     state: &'a State,
+    unit: Unit,
     coin_address: Option<&'a Address>,
     space_address: Option<&'a Address>,
+    labels: &'a Labels,
 ) -> Element<'a, Message> {
-    let address_block: Element<'a, Message> = match if state.coin_address {
+    let address = if state.coin_address {
         coin_address
     } else {
         space_address
-    } {
+    };
+    let address_block: Element<'a, Message> = match address
+        .filter(|_| state.request.is_none())
+        .or(state.request.as_ref())
+    {
         Some(address) => column![
             container(
                 row![
@@ -80,6 +158,11 @@ pub fn view<'a>(
                 .width(300)
                 .height(300)
         ]
+        .push_maybe(
+            address
+                .and_then(|address| labels.address(&address.text))
+                .map(|label| text(format!("Note: {}", label))),
+        )
         .width(Fill)
         .align_x(Center)
         .spacing(10)
@@ -88,18 +171,78 @@ pub fn view<'a>(
     };
 
     center(
-        column![
-            address_block,
-            container(
-                toggler(state.coin_address)
-                    .size(25)
-                    .label("Coins only address")
-                    .on_toggle(Message::AddressKindToggle),
+        Column::new()
+            .push_maybe(state.error.as_ref().map(|error| {
+                container(
+                    text(error)
+                        .style(|theme: &Theme| text::Style {
+                            color: Some(theme.extended_palette().danger.base.text),
+                        })
+                        .center()
+                        .width(Fill),
+                )
+                .style(|theme: &Theme| {
+                    container::Style::default()
+                        .background(theme.extended_palette().danger.base.color)
+                })
+                .width(Fill)
+                .padding([10, 30])
+            }))
+            .push(address_block)
+            .push(
+                container(
+                    toggler(state.coin_address)
+                        .size(25)
+                        .label("Coins only address")
+                        .on_toggle(Message::AddressKindToggle),
+                )
+                .align_x(Center)
+                .width(Fill),
+            )
+            .push(
+                Row::new()
+                    .push_maybe(state.coin_address.then(|| {
+                        column![
+                            text(format!("Requested amount in {}", unit)),
+                            text_input("optional", &state.amount)
+                                .on_input(Message::AmountInput)
+                                .padding(10),
+                        ]
+                        .spacing(5)
+                    }))
+                    .push_maybe(state.coin_address.then(|| {
+                        column![
+                            text("Label shared with the payer"),
+                            text_input("optional", &state.label)
+                                .on_input(Message::LabelInput)
+                                .padding(10),
+                        ]
+                        .spacing(5)
+                    }))
+                    .spacing(10)
+                    .max_width(600),
+            )
+            .push(
+                column![
+                    text("Private note"),
+                    row![
+                        text_input("optional, kept on this device", &state.note)
+                            .on_input(Message::NoteInput)
+                            .padding(10),
+                        button("Save note")
+                            .style(button::secondary)
+                            .on_press_maybe(address.map(|address| Message::NoteSavePress {
+                                address: address.text.clone(),
+                            }))
+                            .padding(10),
+                    ]
+                    .spacing(5),
+                ]
+                .spacing(5)
+                .max_width(600),
             )
             .align_x(Center)
-            .width(Fill),
-        ]
-        .spacing(20),
+            .spacing(20),
     )
     .into()
 }
@@ -1,74 +1,428 @@
-use iced::widget::{button, center, column, container, text, text_input, Column};
+use std::str::FromStr;
+
+use iced::widget::{button, center, column, container, row, scrollable, text, text_input, Column};
 use iced::Alignment::Center;
 use iced::Length::Shrink;
-use iced::{Element, Fill, Theme};
+use iced::{Element, Fill, Font, Theme};
 
-use crate::store::{Amount, Denomination};
+use crate::bip21::Uri;
+use crate::store::{Amount, CoinAddress, Covenant, FeeRate, Network, OutPoint, SLabel};
+use crate::unit::Unit;
+use crate::widget::{expiry, fee_rate, review};
 
 #[derive(Debug, Clone, Default)]
-pub struct State {
+struct Recipient {
     recipient: String,
     amount: String,
+    label: Option<String>,
+}
+
+impl Recipient {
+    fn address(&self) -> Option<String> {
+        if Uri::is_uri(&self.recipient) {
+            Uri::parse(self.recipient.trim()).map(|uri| uri.address)
+        } else {
+            Some(self.recipient.clone())
+        }
+    }
+
+    fn slabel(&self) -> Option<SLabel> {
+        if self.recipient.starts_with('@') {
+            SLabel::from_str(&self.recipient).ok()
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct State {
+    recipients: Vec<Recipient>,
+    fee_rate: fee_rate::State,
+    max: Option<(usize, Amount)>,
+    max_pending: Option<usize>,
+    review: Option<(Vec<(String, Amount)>, Option<FeeRate>)>,
     error: Option<String>,
 }
 
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            recipients: vec![Default::default()],
+            fee_rate: Default::default(),
+            max: None,
+            max_pending: None,
+            review: None,
+            error: None,
+        }
+    }
+}
+
+const TX_OVERHEAD_VBYTES: u64 = 11;
+const INPUT_VBYTES: u64 = 58;
+const OUTPUT_VBYTES: u64 = 43;
+
+pub type RecipientSpace<'a> = Option<(Option<&'a Option<Covenant>>, Option<&'a OutPoint>)>;
+
 impl State {
     pub fn set_error(&mut self, error: String) {
         self.error = Some(error)
     }
+
+    pub fn set_unit(&mut self, from: Unit, to: Unit) {
+        for recipient in &mut self.recipients {
+            recipient.amount = from.convert_input(&recipient.amount, to);
+        }
+    }
+
+    pub fn cancel_max(&mut self) {
+        self.max_pending = None
+    }
+
+    pub fn set_max_amount(&mut self, coins: &[Amount], unit: Unit) {
+        let Some(index) = self.max_pending.take() else {
+            return;
+        };
+        let Some(Some(fee_rate)) = self.fee_rate.fee_rate() else {
+            return;
+        };
+        let total: Amount = coins.iter().copied().sum();
+        let others: Amount = self
+            .recipients
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != index)
+            .filter_map(|(_, recipient)| unit.parse(&recipient.amount))
+            .sum();
+        let vsize = TX_OVERHEAD_VBYTES
+            + INPUT_VBYTES * coins.len() as u64
+            + OUTPUT_VBYTES * self.recipients.len() as u64;
+        match fee_rate.fee_vb(vsize).and_then(|fee| {
+            total
+                .checked_sub(fee)
+                .and_then(|rest| rest.checked_sub(others))
+                .map(|amount| (amount, fee))
+        }) {
+            Some((amount, fee)) if amount > Amount::ZERO => {
+                self.recipients[index].amount = unit.to_input(amount);
+                self.max = Some((index, fee));
+            }
+            _ => {
+                self.max = None;
+                self.error = Some("Balance is too low to cover the fee".into());
+            }
+        }
+    }
+
+    pub fn recipient_slabels(&self) -> impl Iterator<Item = Option<SLabel>> + '_ {
+        self.recipients.iter().map(Recipient::slabel)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
-    RecipientInput(String),
-    AmountInput(String),
+    RecipientInput(usize, String),
+    AmountInput(usize, String),
+    MaxPress(usize),
+    AddRecipientPress,
+    RemoveRecipientPress(usize),
+    FeeRate(fee_rate::Message),
     SendPress,
+    ConfirmPress,
+    CancelPress,
 }
 
 #[derive(Debug, Clone)]
 pub enum Task {
     None,
-    SendCoins { recipient: String, amount: Amount },
+    ResolveSpace {
+        slabel: SLabel,
+    },
+    ListUnspent,
+    SendCoins {
+        recipients: Vec<(String, Amount)>,
+        fee_rate: Option<FeeRate>,
+    },
 }
 
-fn validate(recipient: &String, amount: &String) -> Option<(String, Amount)> {
-    if recipient.is_empty() {
+fn recipient_space_error(recipient_space: RecipientSpace) -> Option<&'static str> {
+    match recipient_space {
+        None | Some((Some(Some(Covenant::Transfer { .. })), _)) => None,
+        Some((None, _)) => Some("Resolving the space"),
+        Some((Some(None), _)) => Some("The space doesn't exist"),
+        Some((Some(Some(Covenant::Reserved)), _)) => Some("The space is reserved"),
+        Some((Some(Some(Covenant::Bid { .. })), _)) => {
+            Some("The space is in auction and has no owner yet")
+        }
+    }
+}
+
+fn space_address_hrp(network: Network) -> &'static str {
+    match network {
+        Network::Bitcoin => "bcs1",
+        Network::Regtest => "bcrts1",
+        _ => "tbs1",
+    }
+}
+
+fn address_error(address: &str, network: Network) -> Option<&'static str> {
+    if address.is_empty() || address.starts_with('@') {
         return None;
     }
-    Amount::from_str_in(amount, Denomination::Satoshi)
-        .ok()
-        .map(|amount| (recipient.clone(), amount))
+    // space addresses are checked by the node
+    if address
+        .to_ascii_lowercase()
+        .starts_with(space_address_hrp(network))
+    {
+        return None;
+    }
+    match CoinAddress::from_str(address) {
+        Ok(address) if address.is_valid_for_network(network) => None,
+        Ok(_) => Some("The address is for a different network"),
+        Err(_) => Some("Invalid address"),
+    }
+}
+
+fn validate(
+    recipients: &[Recipient],
+    unit: Unit,
+    network: Network,
+    fee_rate: &fee_rate::State,
+) -> Option<(Vec<(String, Amount)>, Option<FeeRate>)> {
+    let fee_rate = fee_rate.fee_rate()?;
+    recipients
+        .iter()
+        .map(|recipient| {
+            let address = recipient.address()?;
+            if address.is_empty() || address_error(&address, network).is_some() {
+                None
+            } else {
+                unit.parse(&recipient.amount)
+                    .map(|amount| (address, amount))
+            }
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(|recipients| (recipients, fee_rate))
 }
 
-pub fn update(state: &mut State, message: Message) -> Task {
+pub fn update(state: &mut State, message: Message, unit: Unit, network: Network) -> Task {
     match message {
-        Message::RecipientInput(recipient) => {
-            if recipient
+        Message::RecipientInput(index, recipient) => {
+            if Uri::is_uri(&recipient) {
+                let recipient_row = &mut state.recipients[index];
+                recipient_row.label = None;
+                if let Some(uri) = Uri::parse(recipient.trim()) {
+                    if let Some(amount) = uri.amount {
+                        recipient_row.amount = unit.to_input(amount);
+                        state.max = None;
+                        state.max_pending = None;
+                    }
+                    recipient_row.label = uri.label.or(uri.message);
+                }
+                recipient_row.recipient = recipient;
+            } else if recipient
                 .chars()
-                .all(|c| c.is_ascii_digit() || c.is_ascii_lowercase() || c == '@')
+                .all(|c| c.is_ascii_alphanumeric() || c == '@' || c == '-')
             {
-                state.recipient = recipient;
+                state.recipients[index].recipient = recipient;
+                state.recipients[index].label = None;
+                if let Some(slabel) = state.recipients[index].slabel() {
+                    return Task::ResolveSpace { slabel };
+                }
             }
             Task::None
         }
-        Message::AmountInput(amount) => {
-            if amount.chars().all(|c| c.is_digit(10)) {
-                state.amount = amount
+        Message::AmountInput(index, amount) => {
+            if unit.is_valid_input(&amount) {
+                state.recipients[index].amount = amount;
+                state.max = None;
+                state.max_pending = None;
             }
             Task::None
         }
+        Message::MaxPress(index) => {
+            state.error = None;
+            state.max = None;
+            state.max_pending = Some(index);
+            Task::ListUnspent
+        }
+        Message::AddRecipientPress => {
+            state.recipients.push(Default::default());
+            state.max = None;
+            state.max_pending = None;
+            Task::None
+        }
+        Message::RemoveRecipientPress(index) => {
+            if state.recipients.len() > 1 {
+                state.recipients.remove(index);
+                state.max = None;
+                state.max_pending = None;
+            }
+            Task::None
+        }
+        Message::FeeRate(message) => {
+            fee_rate::update(&mut state.fee_rate, message);
+            state.max = None;
+            state.max_pending = None;
+            Task::None
+        }
         Message::SendPress => {
             state.error = None;
-            if let Some((recipient, amount)) = validate(&state.recipient, &state.amount) {
-                Task::SendCoins { recipient, amount }
+            state.review = validate(&state.recipients, unit, network, &state.fee_rate);
+            Task::None
+        }
+        Message::ConfirmPress => {
+            if let Some((recipients, fee_rate)) = state.review.take() {
+                Task::SendCoins {
+                    recipients,
+                    fee_rate,
+                }
             } else {
                 Task::None
             }
         }
+        Message::CancelPress => {
+            state.review = None;
+            Task::None
+        }
     }
 }
 
-pub fn view<'a>(state: &'a State) -> Element<'a, Message> {
+pub fn view<'a>(
+    state: &'a State,
+    unit: Unit,
+    network: Network,
+    tip_height: u32,
+    balance: Amount,
+    recipient_spaces: Vec<RecipientSpace<'a>>,
+) -> Element<'a, Message> {
+    if let Some((recipients, fee_rate)) = state.review.as_ref() {
+        let total: Amount = recipients.iter().map(|(_, amount)| *amount).sum();
+        let mut rows = Vec::new();
+        for (index, (recipient, amount)) in recipients.iter().enumerate() {
+            rows.push(("Recipient", recipient.clone()));
+            if state.max.is_some_and(|(i, _)| i == index) {
+                rows.push(("Amount (estimated)", unit.format(*amount)));
+            } else {
+                rows.push(("Amount", unit.format(*amount)));
+            }
+        }
+        if recipients.len() > 1 {
+            rows.push(("Total", unit.format(total)));
+        }
+        if let Some((_, fee)) = state.max {
+            rows.push(("Estimated fee", unit.format(fee)));
+        }
+        rows.extend([
+            ("Fee rate", fee_rate::label(*fee_rate)),
+            ("Balance", unit.format(balance)),
+            (
+                "Balance after",
+                match state.max {
+                    Some((_, fee)) => balance.checked_sub(total + fee).map_or_else(
+                        || "Insufficient funds".into(),
+                        |balance| format!("About {}", unit.format(balance)),
+                    ),
+                    None => balance.checked_sub(total).map_or_else(
+                        || "Insufficient funds".into(),
+                        |balance| format!("{} minus fee", unit.format(balance)),
+                    ),
+                },
+            ),
+        ]);
+        return center(scrollable(review::view(
+            "Review transaction".into(),
+            rows,
+            Message::ConfirmPress,
+            Message::CancelPress,
+        )))
+        .padding(20)
+        .into();
+    }
+
+    let has_space_error = recipient_spaces
+        .iter()
+        .any(|recipient_space| recipient_space_error(*recipient_space).is_some());
+    let can_max = matches!(state.fee_rate.fee_rate(), Some(Some(_)));
+    let can_remove = state.recipients.len() > 1;
+
+    let recipient_form =
+        |index: usize, recipient: &'a Recipient, recipient_space: RecipientSpace<'a>| {
+            let space_info: Option<Element<'a, Message>> = match recipient_space {
+                Some((Some(Some(Covenant::Transfer { expire_height, .. })), outpoint)) => Some(
+                    column![text("The space is registered")]
+                        .push_maybe(outpoint.map(|outpoint| {
+                            text(format!("Owner: {}", outpoint)).font(Font::MONOSPACE)
+                        }))
+                        .push(expiry::view(tip_height, *expire_height))
+                        .spacing(5)
+                        .into(),
+                ),
+                Some(_) => recipient_space_error(recipient_space).map(|error| {
+                    text(error)
+                        .style(|theme: &Theme| text::Style {
+                            color: Some(theme.extended_palette().danger.base.color),
+                        })
+                        .into()
+                }),
+                None => recipient
+                    .address()
+                    .map_or(Some("Invalid payment URI"), |address| {
+                        address_error(&address, network)
+                    })
+                    .filter(|_| !recipient.recipient.is_empty())
+                    .map(|error| {
+                        text(error)
+                            .style(|theme: &Theme| text::Style {
+                                color: Some(theme.extended_palette().danger.base.color),
+                            })
+                            .into()
+                    }),
+            };
+
+            column![
+                text("Recipient address"),
+                row![text_input("", &recipient.recipient)
+                    .on_input(move |recipient| Message::RecipientInput(index, recipient))
+                    .padding(10)]
+                .push_maybe(can_remove.then(|| {
+                    button("Remove")
+                        .style(button::secondary)
+                        .on_press(Message::RemoveRecipientPress(index))
+                        .padding(10)
+                }))
+                .spacing(5),
+            ]
+            .push_maybe(space_info)
+            .push_maybe(
+                recipient
+                    .label
+                    .as_ref()
+                    .map(|label| text(format!("Label: {}", label))),
+            )
+            .push(text(format!("Amount in {}", unit)))
+            .push(
+                row![
+                    text_input("", &recipient.amount)
+                        .on_input(move |amount| Message::AmountInput(index, amount))
+                        .padding(10),
+                    button("Max")
+                        .style(button::secondary)
+                        .on_press_maybe(can_max.then_some(Message::MaxPress(index)))
+                        .padding(10),
+                ]
+                .spacing(5),
+            )
+            .push_maybe(state.max.filter(|(i, _)| *i == index).map(|(_, fee)| {
+                text(format!(
+                    "Estimated from the whole balance minus a fee of about {}, \
+                     the node may reject it if the actual fee is higher",
+                    unit.format(fee)
+                ))
+            }))
+            .spacing(5)
+        };
+
     center(
         Column::new()
             .push_maybe(state.error.as_ref().map(|error| {
@@ -87,24 +441,36 @@ pub fn view<'a>(state: &'a State) -> Element<'a, Message> {
                 .width(Fill)
                 .padding([10, 30])
             }))
+            .push(scrollable(
+                Column::with_children(
+                    state
+                        .recipients
+                        .iter()
+                        .zip(recipient_spaces)
+                        .enumerate()
+                        .map(|(index, (recipient, recipient_space))| {
+                            recipient_form(index, recipient, recipient_space).into()
+                        }),
+                )
+                .spacing(20),
+            ))
             .push(
-                column![
-                    text("Recipient address"),
-                    text_input("", &state.recipient)
-                        .on_input(Message::RecipientInput)
-                        .padding(10),
-                    text("Amount in SAT"),
-                    text_input("", &state.amount)
-                        .on_input(Message::AmountInput)
-                        .padding(10),
-                ]
-                .spacing(5),
+                button("Add recipient")
+                    .style(button::secondary)
+                    .on_press(Message::AddRecipientPress)
+                    .padding([5, 10]),
+            )
+            .push(fee_rate::view(&state.fee_rate).map(Message::FeeRate))
+            .push_maybe(
+                (!can_max).then(|| text("Select a valid fee rate other than Auto to use Max")),
             )
             .push(
                 container(
                     button("Send")
                         .on_press_maybe(
-                            validate(&state.recipient, &state.amount).map(|_| Message::SendPress),
+                            validate(&state.recipients, unit, network, &state.fee_rate)
+                                .filter(|_| !has_space_error)
+                                .map(|_| Message::SendPress),
                         )
                         .padding([10, 20])
                         .width(Shrink),
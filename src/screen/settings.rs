@@ -0,0 +1,254 @@
+use std::path::PathBuf;
+
+use iced::widget::{
+    button, center, column, container, pick_list, row, text, text_input, Column, Row,
+};
+use iced::{Center, Element, Fill, Font, Shrink, Theme};
+
+use crate::auth::RpcAuth;
+use crate::unit::Unit;
+
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    chain: String,
+    spaced_rpc_url: String,
+    wallet: String,
+    rpc_user: String,
+    rpc_password: String,
+    rpc_cookie: String,
+    saved: bool,
+    error: Option<String>,
+}
+
+impl State {
+    pub fn new(chain: String, spaced_rpc_url: String, wallet: String, rpc_auth: &RpcAuth) -> Self {
+        let mut state = Self {
+            chain,
+            spaced_rpc_url,
+            wallet,
+            ..Default::default()
+        };
+        match rpc_auth {
+            RpcAuth::None => {}
+            RpcAuth::UserPass { user, password } => {
+                state.rpc_user = user.clone();
+                state.rpc_password = password.clone();
+            }
+            RpcAuth::Cookie(path) => state.rpc_cookie = path.display().to_string(),
+        }
+        state
+    }
+
+    pub fn is_saved(&self) -> bool {
+        self.saved
+    }
+
+    pub fn set_saved(&mut self) {
+        self.saved = true
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ChainSelect(String),
+    SpacedRpcUrlInput(String),
+    WalletInput(String),
+    RpcUserInput(String),
+    RpcPasswordInput(String),
+    RpcCookieInput(String),
+    ThemeSelect(Theme),
+    UnitSelect(Unit),
+    SavePress,
+    BackPress,
+}
+
+#[derive(Debug, Clone)]
+pub enum Task {
+    None,
+    SetTheme(Theme),
+    SetUnit(Unit),
+    SaveConnection {
+        chain: String,
+        spaced_rpc_url: String,
+        wallet: String,
+        rpc_auth: RpcAuth,
+    },
+    Back,
+}
+
+fn validate(state: &State) -> Option<Task> {
+    if state.spaced_rpc_url.is_empty()
+        || state.wallet.is_empty()
+        || (!state.rpc_cookie.is_empty() && !state.rpc_user.is_empty())
+        || (!state.rpc_password.is_empty() && state.rpc_user.is_empty())
+    {
+        return None;
+    }
+    Some(Task::SaveConnection {
+        chain: state.chain.clone(),
+        spaced_rpc_url: state.spaced_rpc_url.clone(),
+        wallet: state.wallet.clone(),
+        rpc_auth: RpcAuth::new(
+            Some(state.rpc_user.clone()).filter(|user| !user.is_empty()),
+            Some(state.rpc_password.clone()).filter(|password| !password.is_empty()),
+            Some(state.rpc_cookie.trim())
+                .filter(|cookie| !cookie.is_empty())
+                .map(PathBuf::from),
+        ),
+    })
+}
+
+pub fn update(state: &mut State, message: Message) -> Task {
+    state.error = None;
+    match message {
+        Message::ChainSelect(chain) => {
+            state.saved = false;
+            state.chain = chain;
+            Task::None
+        }
+        Message::SpacedRpcUrlInput(spaced_rpc_url) => {
+            state.saved = false;
+            state.spaced_rpc_url = spaced_rpc_url;
+            Task::None
+        }
+        Message::WalletInput(wallet) => {
+            if wallet
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                state.saved = false;
+                state.wallet = wallet;
+            }
+            Task::None
+        }
+        Message::RpcUserInput(rpc_user) => {
+            state.saved = false;
+            state.rpc_user = rpc_user;
+            Task::None
+        }
+        Message::RpcPasswordInput(rpc_password) => {
+            state.saved = false;
+            state.rpc_password = rpc_password;
+            Task::None
+        }
+        Message::RpcCookieInput(rpc_cookie) => {
+            state.saved = false;
+            state.rpc_cookie = rpc_cookie;
+            Task::None
+        }
+        Message::ThemeSelect(theme) => Task::SetTheme(theme),
+        Message::UnitSelect(unit) => Task::SetUnit(unit),
+        Message::SavePress => validate(state).unwrap_or(Task::None),
+        Message::BackPress => Task::Back,
+    }
+}
+
+pub fn view<'a>(
+    state: &'a State,
+    chains: Vec<String>,
+    theme: Theme,
+    unit: Unit,
+    has_wallet: bool,
+) -> Element<'a, Message> {
+    center(
+        Column::new()
+            .push_maybe(state.error.as_ref().map(|error| {
+                container(
+                    text(error)
+                        .style(|theme: &Theme| text::Style {
+                            color: Some(theme.extended_palette().danger.base.text),
+                        })
+                        .center()
+                        .width(Fill),
+                )
+                .style(|theme: &Theme| {
+                    container::Style::default()
+                        .background(theme.extended_palette().danger.base.color)
+                })
+                .width(Fill)
+                .padding([10, 30])
+            }))
+            .push(
+                column![
+                    text("Chain"),
+                    pick_list(chains, Some(state.chain.clone()), Message::ChainSelect)
+                        .padding(10)
+                        .width(Fill),
+                    text("Spaced RPC URL"),
+                    text_input("http://127.0.0.1:7225", &state.spaced_rpc_url)
+                        .on_input(Message::SpacedRpcUrlInput)
+                        .font(Font::MONOSPACE)
+                        .padding(10),
+                    text("Wallet"),
+                    text_input("default", &state.wallet)
+                        .on_input(Message::WalletInput)
+                        .font(Font::MONOSPACE)
+                        .padding(10),
+                    text("RPC user and password (stored in the config file)"),
+                    row![
+                        text_input("user", &state.rpc_user)
+                            .on_input(Message::RpcUserInput)
+                            .padding(10),
+                        text_input("password", &state.rpc_password)
+                            .on_input(Message::RpcPasswordInput)
+                            .secure(true)
+                            .padding(10),
+                    ]
+                    .spacing(5),
+                    text("Or RPC cookie file"),
+                    text_input("path to .cookie", &state.rpc_cookie)
+                        .on_input(Message::RpcCookieInput)
+                        .font(Font::MONOSPACE)
+                        .padding(10),
+                ]
+                .spacing(5),
+            )
+            .push(
+                container(
+                    Row::new()
+                        .push_maybe((!has_wallet).then(|| {
+                            button("Back")
+                                .style(button::secondary)
+                                .on_press(Message::BackPress)
+                                .padding([10, 20])
+                                .width(Shrink)
+                        }))
+                        .push(
+                            button("Save to config and connect")
+                                .on_press_maybe(validate(state).map(|_| Message::SavePress))
+                                .padding([10, 20])
+                                .width(Shrink),
+                        )
+                        .spacing(10),
+                )
+                .align_x(Center)
+                .width(Fill),
+            )
+            .push_maybe(state.saved.then(|| {
+                text("Connection saved to the config file")
+                    .width(Fill)
+                    .center()
+            }))
+            .push(
+                column![
+                    text("Theme"),
+                    pick_list(Theme::ALL, Some(theme), Message::ThemeSelect)
+                        .padding(10)
+                        .width(Fill),
+                    text("Denomination"),
+                    pick_list(Unit::ALL, Some(unit), Message::UnitSelect)
+                        .padding(10)
+                        .width(Fill),
+                ]
+                .spacing(5),
+            )
+            .spacing(10)
+            .max_width(600),
+    )
+    .padding(20)
+    .into()
+}
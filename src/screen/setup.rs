@@ -0,0 +1,231 @@
+use iced::widget::{
+    button, center, checkbox, column, container, row, scrollable, text, text_input, Column,
+};
+use iced::{Border, Center, Element, Fill, Font, Shrink, Theme};
+
+use crate::icon;
+
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    wallet_name: String,
+    loading: bool,
+    recovery: Option<String>,
+    recovery_confirmed: bool,
+    error: Option<String>,
+}
+
+impl State {
+    pub fn new(wallet_name: String) -> Self {
+        Self {
+            wallet_name,
+            loading: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.loading = false;
+        self.error = Some(error)
+    }
+
+    pub fn set_recovery(&mut self, recovery: String) {
+        self.loading = false;
+        self.recovery = Some(recovery);
+        self.recovery_confirmed = false;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    WalletNameInput(String),
+    CreatePress,
+    LoadPress,
+    ImportPress,
+    SettingsPress,
+    CopyPress(String),
+    RecoveryConfirmedToggle(bool),
+    ContinuePress,
+}
+
+#[derive(Debug, Clone)]
+pub enum Task {
+    None,
+    CreateWallet { wallet: String },
+    LoadWallet { wallet: String },
+    Import,
+    Settings,
+    WriteClipboard(String),
+}
+
+fn validate(wallet_name: &String) -> Option<String> {
+    if wallet_name.is_empty() {
+        None
+    } else {
+        Some(wallet_name.clone())
+    }
+}
+
+pub fn update(state: &mut State, message: Message) -> Task {
+    match message {
+        Message::WalletNameInput(wallet_name) => {
+            if wallet_name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                state.wallet_name = wallet_name;
+            }
+            Task::None
+        }
+        Message::CreatePress => {
+            state.error = None;
+            if let Some(wallet) = validate(&state.wallet_name) {
+                state.loading = true;
+                Task::CreateWallet { wallet }
+            } else {
+                Task::None
+            }
+        }
+        Message::LoadPress => {
+            state.error = None;
+            if let Some(wallet) = validate(&state.wallet_name) {
+                state.loading = true;
+                Task::LoadWallet { wallet }
+            } else {
+                Task::None
+            }
+        }
+        Message::ImportPress => {
+            state.error = None;
+            Task::Import
+        }
+        Message::SettingsPress => {
+            state.error = None;
+            Task::Settings
+        }
+        Message::CopyPress(s) => Task::WriteClipboard(s),
+        Message::RecoveryConfirmedToggle(recovery_confirmed) => {
+            state.recovery_confirmed = recovery_confirmed;
+            Task::None
+        }
+        Message::ContinuePress => {
+            if state.recovery_confirmed {
+                state.recovery = None;
+                state.recovery_confirmed = false;
+                state.loading = true;
+                Task::LoadWallet {
+                    wallet: state.wallet_name.clone(),
+                }
+            } else {
+                Task::None
+            }
+        }
+    }
+}
+
+pub fn view<'a>(state: &'a State) -> Element<'a, Message> {
+    if state.loading {
+        return center(text("Loading").align_x(Center)).into();
+    }
+
+    let main: Element<'a, Message> = if let Some(recovery) = state.recovery.as_ref() {
+        column![
+            text(format!(
+                "Wallet \"{}\" was created. Write down the recovery data below and keep it in a safe place, it won't be shown again.",
+                state.wallet_name
+            )),
+            container(
+                row![
+                    scrollable(text(recovery).font(Font::MONOSPACE)).width(Fill),
+                    button(text(icon::COPY).font(icon::FONT))
+                        .style(button::secondary)
+                        .on_press(Message::CopyPress(recovery.clone())),
+                ]
+                .spacing(5),
+            )
+            .padding(10)
+            .max_height(200)
+            .style(|theme: &Theme| {
+                let palette = theme.extended_palette();
+                container::Style::default().border(Border {
+                    color: palette.secondary.base.text,
+                    width: 1.0,
+                    radius: 0.into(),
+                })
+            }),
+            checkbox("I wrote it down", state.recovery_confirmed)
+                .on_toggle(Message::RecoveryConfirmedToggle),
+            container(
+                button("Continue")
+                    .on_press_maybe(state.recovery_confirmed.then_some(Message::ContinuePress))
+                    .padding([10, 20])
+                    .width(Shrink),
+            )
+            .align_x(Center)
+            .width(Fill),
+        ]
+        .spacing(10)
+        .into()
+    } else {
+        let wallet = validate(&state.wallet_name);
+        column![
+            text("Wallet name"),
+            text_input("default", &state.wallet_name)
+                .on_input(Message::WalletNameInput)
+                .font(Font::MONOSPACE)
+                .padding(10),
+            container(
+                row![
+                    button("Load")
+                        .style(button::secondary)
+                        .on_press_maybe(wallet.as_ref().map(|_| Message::LoadPress))
+                        .padding([10, 20])
+                        .width(Shrink),
+                    button("Create")
+                        .on_press_maybe(wallet.as_ref().map(|_| Message::CreatePress))
+                        .padding([10, 20])
+                        .width(Shrink),
+                    button("Import")
+                        .style(button::secondary)
+                        .on_press(Message::ImportPress)
+                        .padding([10, 20])
+                        .width(Shrink),
+                    button("Settings")
+                        .style(button::secondary)
+                        .on_press(Message::SettingsPress)
+                        .padding([10, 20])
+                        .width(Shrink),
+                ]
+                .spacing(10),
+            )
+            .align_x(Center)
+            .width(Fill),
+        ]
+        .spacing(10)
+        .into()
+    };
+
+    center(
+        Column::new()
+            .push_maybe(state.error.as_ref().map(|error| {
+                container(
+                    text(error)
+                        .style(|theme: &Theme| text::Style {
+                            color: Some(theme.extended_palette().danger.base.text),
+                        })
+                        .center()
+                        .width(Fill),
+                )
+                .style(|theme: &Theme| {
+                    container::Style::default()
+                        .background(theme.extended_palette().danger.base.color)
+                })
+                .width(Fill)
+                .padding([10, 30])
+            }))
+            .push(main)
+            .spacing(10)
+            .max_width(600),
+    )
+    .padding(20)
+    .into()
+}
@@ -1,12 +1,19 @@
-use iced::widget::{button, center, column, container, text, text_input, Column};
+use iced::widget::{button, center, column, container, row, scrollable, text, text_input, Column};
 use iced::{Center, Element, Fill, Font, Shrink, Theme};
 
 use crate::icon;
-use crate::store::{Amount, Covenant, Denomination, SLabel};
+use crate::store::{Amount, Covenant, FeeRate, SLabel};
+use crate::unit::Unit;
+use crate::widget::{expiry, fee_rate, review};
 
 #[derive(Debug, Clone, Default)]
 pub struct State {
     bid_amount: String,
+    register_recipient: String,
+    transfer_recipient: String,
+    fee_rate: fee_rate::State,
+    basket: Vec<(SLabel, Amount, bool)>,
+    review: Option<Task>,
     error: Option<String>,
 }
 
@@ -14,14 +21,40 @@ impl State {
     pub fn set_error(&mut self, error: String) {
         self.error = Some(error)
     }
+
+    pub fn set_unit(&mut self, from: Unit, to: Unit) {
+        self.bid_amount = from.convert_input(&self.bid_amount, to);
+    }
+
+    pub fn reset_space(&mut self) {
+        self.bid_amount.clear();
+        self.register_recipient.clear();
+        self.transfer_recipient.clear();
+        self.review = None;
+        self.error = None;
+    }
+
+    pub fn clear_basket(&mut self) {
+        self.basket.clear()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     SpaceNameInput(String),
     BidAmountInput(String),
+    FeeRate(fee_rate::Message),
     BidPress(SLabel, bool),
+    AddToBasketPress(SLabel, bool),
+    RemoveFromBasketPress(usize),
+    BasketReviewPress,
+    RegisterRecipientInput(String),
     RegisterPress(SLabel),
+    TransferRecipientInput(String),
+    TransferPress(SLabel),
+    RenewPress(SLabel),
+    ConfirmPress,
+    CancelPress,
 }
 
 #[derive(Debug, Clone)]
@@ -34,17 +67,50 @@ pub enum Task {
         slabel: SLabel,
         amount: Amount,
         open: bool,
+        fee_rate: Option<FeeRate>,
+    },
+    BidSpaces {
+        bids: Vec<(SLabel, Amount, bool)>,
+        fee_rate: Option<FeeRate>,
     },
     RegisterSpace {
         slabel: SLabel,
+        recipient: Option<String>,
+        fee_rate: Option<FeeRate>,
+    },
+    TransferSpace {
+        slabel: SLabel,
+        recipient: String,
+        fee_rate: Option<FeeRate>,
+    },
+    RenewSpace {
+        slabel: SLabel,
+        fee_rate: Option<FeeRate>,
     },
 }
 
-fn validate(bid_amount: &String) -> Option<Amount> {
-    Amount::from_str_in(bid_amount, Denomination::Satoshi).ok()
+fn validate(
+    bid_amount: &String,
+    unit: Unit,
+    fee_rate: &fee_rate::State,
+) -> Option<(Amount, Option<FeeRate>)> {
+    let fee_rate = fee_rate.fee_rate()?;
+    unit.parse(bid_amount).map(|amount| (amount, fee_rate))
 }
 
-pub fn update(state: &mut State, message: Message) -> Task {
+fn validate_transfer(
+    recipient: &String,
+    fee_rate: &fee_rate::State,
+) -> Option<(String, Option<FeeRate>)> {
+    if recipient.is_empty() || recipient == "@" {
+        return None;
+    }
+    fee_rate
+        .fee_rate()
+        .map(|fee_rate| (recipient.clone(), fee_rate))
+}
+
+pub fn update(state: &mut State, message: Message, unit: Unit) -> Task {
     state.error = None;
     match message {
         Message::SpaceNameInput(space_name) => {
@@ -52,38 +118,225 @@ pub fn update(state: &mut State, message: Message) -> Task {
                 .chars()
                 .all(|c| c.is_ascii_digit() || c.is_ascii_lowercase() || c == '-')
             {
+                state.review = None;
                 Task::SetSpace { space_name }
             } else {
                 Task::None
             }
         }
         Message::BidAmountInput(bid_amount) => {
-            if bid_amount.chars().all(|c| c.is_digit(10) || c == '.') {
+            if unit.is_valid_input(&bid_amount) {
                 state.bid_amount = bid_amount
             }
             Task::None
         }
+        Message::FeeRate(message) => {
+            fee_rate::update(&mut state.fee_rate, message);
+            Task::None
+        }
         Message::BidPress(slabel, open) => {
-            if let Some(amount) = validate(&state.bid_amount) {
-                Task::BidSpace {
+            state.review =
+                validate(&state.bid_amount, unit, &state.fee_rate).map(|(amount, fee_rate)| {
+                    Task::BidSpace {
+                        slabel,
+                        amount,
+                        open,
+                        fee_rate,
+                    }
+                });
+            Task::None
+        }
+        Message::AddToBasketPress(slabel, open) => {
+            if let Some(amount) = unit.parse(&state.bid_amount) {
+                state.basket.retain(|(s, _, _)| *s != slabel);
+                state.basket.push((slabel, amount, open));
+                state.bid_amount.clear();
+            }
+            Task::None
+        }
+        Message::RemoveFromBasketPress(index) => {
+            if index < state.basket.len() {
+                state.basket.remove(index);
+            }
+            Task::None
+        }
+        Message::BasketReviewPress => {
+            state.review = state
+                .fee_rate
+                .fee_rate()
+                .filter(|_| !state.basket.is_empty())
+                .map(|fee_rate| Task::BidSpaces {
+                    bids: state.basket.clone(),
+                    fee_rate,
+                });
+            Task::None
+        }
+        Message::RegisterRecipientInput(recipient) => {
+            if recipient
+                .chars()
+                .all(|c| c.is_ascii_digit() || c.is_ascii_lowercase())
+            {
+                state.register_recipient = recipient;
+            }
+            Task::None
+        }
+        Message::RegisterPress(slabel) => {
+            state.review = state
+                .fee_rate
+                .fee_rate()
+                .map(|fee_rate| Task::RegisterSpace {
                     slabel,
-                    amount,
-                    open,
-                }
-            } else {
-                Task::None
+                    recipient: Some(state.register_recipient.clone())
+                        .filter(|recipient| !recipient.is_empty()),
+                    fee_rate,
+                });
+            Task::None
+        }
+        Message::TransferRecipientInput(recipient) => {
+            if recipient
+                .chars()
+                .all(|c| c.is_ascii_digit() || c.is_ascii_lowercase() || c == '@' || c == '-')
+            {
+                state.transfer_recipient = recipient;
             }
+            Task::None
+        }
+        Message::TransferPress(slabel) => {
+            state.review = validate_transfer(&state.transfer_recipient, &state.fee_rate).map(
+                |(recipient, fee_rate)| Task::TransferSpace {
+                    slabel,
+                    recipient,
+                    fee_rate,
+                },
+            );
+            Task::None
+        }
+        Message::RenewPress(slabel) => {
+            state.review = state
+                .fee_rate
+                .fee_rate()
+                .map(|fee_rate| Task::RenewSpace { slabel, fee_rate });
+            Task::None
         }
-        Message::RegisterPress(slabel) => Task::RegisterSpace { slabel },
+        Message::ConfirmPress => state.review.take().unwrap_or(Task::None),
+        Message::CancelPress => {
+            state.review = None;
+            Task::None
+        }
+    }
+}
+
+fn review_rows(
+    task: &Task,
+    balance: Amount,
+    unit: Unit,
+) -> Option<(String, Vec<(&'static str, String)>)> {
+    let balance_row = |spent: Amount| {
+        (
+            "Balance after",
+            balance.checked_sub(spent).map_or_else(
+                || "Insufficient funds".into(),
+                |balance| format!("{} minus fee", unit.format(balance)),
+            ),
+        )
+    };
+    match task {
+        Task::BidSpace {
+            slabel,
+            amount,
+            open,
+            fee_rate,
+        } => Some((
+            format!("Review {} {}", if *open { "open" } else { "bid" }, slabel),
+            vec![
+                ("Space", slabel.to_string()),
+                ("Amount", unit.format(*amount)),
+                ("Fee rate", fee_rate::label(*fee_rate)),
+                balance_row(*amount),
+            ],
+        )),
+        Task::BidSpaces { bids, fee_rate } => {
+            let total: Amount = bids.iter().map(|(_, amount, _)| *amount).sum();
+            let mut rows: Vec<_> = bids
+                .iter()
+                .map(|(slabel, amount, open)| {
+                    (
+                        if *open { "Open" } else { "Bid" },
+                        format!("{} {}", slabel, unit.format(*amount)),
+                    )
+                })
+                .collect();
+            rows.extend([
+                ("Total", unit.format(total)),
+                ("Fee rate", fee_rate::label(*fee_rate)),
+                balance_row(total),
+            ]);
+            Some(("Review bids".into(), rows))
+        }
+        Task::RegisterSpace {
+            slabel,
+            recipient,
+            fee_rate,
+        } => Some((
+            format!("Review register {}", slabel),
+            vec![
+                ("Space", slabel.to_string()),
+                (
+                    "Register to",
+                    recipient.clone().unwrap_or_else(|| "This wallet".into()),
+                ),
+                ("Fee rate", fee_rate::label(*fee_rate)),
+                balance_row(Amount::ZERO),
+            ],
+        )),
+        Task::TransferSpace {
+            slabel,
+            recipient,
+            fee_rate,
+        } => Some((
+            format!("Review transfer {}", slabel),
+            vec![
+                ("Space", slabel.to_string()),
+                ("Transfer to", recipient.clone()),
+                ("Fee rate", fee_rate::label(*fee_rate)),
+                balance_row(Amount::ZERO),
+            ],
+        )),
+        Task::RenewSpace { slabel, fee_rate } => Some((
+            format!("Review renew {}", slabel),
+            vec![
+                ("Space", slabel.to_string()),
+                ("Fee rate", fee_rate::label(*fee_rate)),
+                balance_row(Amount::ZERO),
+            ],
+        )),
+        Task::None | Task::SetSpace { .. } => None,
     }
 }
 
 pub fn view<'a>(
     state: &'a State,
+    unit: Unit,
     tip_height: u32,
+    balance: Amount,
     space_name: &'a String,
     space_data: Option<(SLabel, Option<&'a Option<Covenant>>, bool)>,
 ) -> Element<'a, Message> {
+    if let Some((title, rows)) = state
+        .review
+        .as_ref()
+        .and_then(|task| review_rows(task, balance, unit))
+    {
+        return center(scrollable(review::view(
+            title,
+            rows,
+            Message::ConfirmPress,
+            Message::CancelPress,
+        )))
+        .padding(20)
+        .into();
+    }
+
     let bid_form = |slabel: SLabel, total_burned: Option<&Amount>| {
         Column::new()
             .push_maybe(state.error.as_ref().map(|error| {
@@ -107,7 +360,7 @@ pub fn view<'a>(
                     if let Some(total_burned) = total_burned {
                         text(format!(
                             "The space current bid is {}",
-                            total_burned.to_string_with_denomination(Denomination::Satoshi)
+                            unit.format(*total_burned)
                         ))
                     } else {
                         text("This space doesn't exist. You can open it.")
@@ -115,22 +368,33 @@ pub fn view<'a>(
                     text_input("amount", &state.bid_amount)
                         .on_input(Message::BidAmountInput)
                         .padding(10),
+                    fee_rate::view(&state.fee_rate).map(Message::FeeRate),
                 ]
                 .spacing(5),
             )
             .push(
                 container(
-                    button(if total_burned.is_none() {
-                        "Open"
-                    } else {
-                        "Bid"
-                    })
-                    .on_press_maybe(
-                        validate(&state.bid_amount)
-                            .map(|_| Message::BidPress(slabel.clone(), total_burned.is_none())),
-                    )
-                    .padding([10, 20])
-                    .width(Shrink),
+                    row![
+                        button("Add to basket")
+                            .style(button::secondary)
+                            .on_press_maybe(unit.parse(&state.bid_amount).map(|_| {
+                                Message::AddToBasketPress(slabel.clone(), total_burned.is_none())
+                            }))
+                            .padding([10, 20])
+                            .width(Shrink),
+                        button(if total_burned.is_none() {
+                            "Open"
+                        } else {
+                            "Bid"
+                        })
+                        .on_press_maybe(
+                            validate(&state.bid_amount, unit, &state.fee_rate)
+                                .map(|_| Message::BidPress(slabel, total_burned.is_none())),
+                        )
+                        .padding([10, 20])
+                        .width(Shrink),
+                    ]
+                    .spacing(10),
                 )
                 .align_x(Center)
                 .width(Fill),
@@ -157,10 +421,26 @@ pub fn view<'a>(
                 .padding([10, 30])
             }))
             .push(text("You can claim the space.").align_x(Center))
+            .push(
+                column![
+                    text("Register to (leave empty to keep the space in this wallet)"),
+                    text_input("space address", &state.register_recipient)
+                        .on_input(Message::RegisterRecipientInput)
+                        .font(Font::MONOSPACE)
+                        .padding(10),
+                ]
+                .spacing(5),
+            )
+            .push(fee_rate::view(&state.fee_rate).map(Message::FeeRate))
             .push(
                 container(
                     button("Register")
-                        .on_press(Message::RegisterPress(slabel))
+                        .on_press_maybe(
+                            state
+                                .fee_rate
+                                .fee_rate()
+                                .map(|_| Message::RegisterPress(slabel)),
+                        )
                         .padding([10, 20])
                         .width(Shrink),
                 )
@@ -170,6 +450,65 @@ pub fn view<'a>(
             .spacing(10)
     };
 
+    let transfer_form = |slabel: SLabel, expire_height: u32| {
+        Column::new()
+            .push_maybe(state.error.as_ref().map(|error| {
+                container(
+                    text(error)
+                        .style(|theme: &Theme| text::Style {
+                            color: Some(theme.extended_palette().danger.base.text),
+                        })
+                        .center()
+                        .width(Fill),
+                )
+                .style(|theme: &Theme| {
+                    container::Style::default()
+                        .background(theme.extended_palette().danger.base.color)
+                })
+                .width(Fill)
+                .padding([10, 30])
+            }))
+            .push(
+                column![
+                    text("The space is registered by you. You can transfer or renew it."),
+                    expiry::view(tip_height, expire_height),
+                    text_input("space address or @space", &state.transfer_recipient)
+                        .on_input(Message::TransferRecipientInput)
+                        .font(Font::MONOSPACE)
+                        .padding(10),
+                    fee_rate::view(&state.fee_rate).map(Message::FeeRate),
+                ]
+                .spacing(5),
+            )
+            .push(
+                container(
+                    row![
+                        button("Renew")
+                            .style(button::secondary)
+                            .on_press_maybe(
+                                state
+                                    .fee_rate
+                                    .fee_rate()
+                                    .map(|_| Message::RenewPress(slabel.clone())),
+                            )
+                            .padding([10, 20])
+                            .width(Shrink),
+                        button("Transfer")
+                            .on_press_maybe(
+                                validate_transfer(&state.transfer_recipient, &state.fee_rate)
+                                    .map(|_| Message::TransferPress(slabel)),
+                            )
+                            .padding([10, 20])
+                            .width(Shrink),
+                    ]
+                    .spacing(10),
+                )
+                .align_x(Center)
+                .width(Fill),
+            )
+            .spacing(10)
+    };
+
     println!("{:?}", &space_data);
 
     let main: Element<'a, Message> = match space_data {
@@ -200,15 +539,61 @@ pub fn view<'a>(
                 bid_form(slabel, Some(total_burned)).into()
             }
         }
-        Some((_, Some(Some(Covenant::Transfer { .. })), is_owned)) => {
+        Some((slabel, Some(Some(Covenant::Transfer { expire_height, .. })), is_owned)) => {
             if is_owned {
-                text("The space is registered by you.").into()
+                transfer_form(slabel, *expire_height).into()
             } else {
-                text("The space is already registered.").into()
+                column![
+                    text("The space is already registered."),
+                    expiry::view(tip_height, *expire_height),
+                ]
+                .spacing(5)
+                .align_x(Center)
+                .into()
             }
         }
     };
 
+    let basket = (!state.basket.is_empty()).then(|| {
+        container(
+            column![
+                text("Bid basket"),
+                Column::with_children(state.basket.iter().enumerate().map(
+                    |(index, (slabel, amount, open))| {
+                        row![
+                            text(slabel.to_string()).font(Font::MONOSPACE).width(Fill),
+                            text(if *open { "Open" } else { "Bid" }),
+                            text(unit.format(*amount)),
+                            button("Remove")
+                                .style(button::secondary)
+                                .on_press(Message::RemoveFromBasketPress(index)),
+                        ]
+                        .spacing(10)
+                        .align_y(Center)
+                        .into()
+                    },
+                ))
+                .spacing(5),
+                fee_rate::view(&state.fee_rate).map(Message::FeeRate),
+                container(
+                    button("Review basket")
+                        .on_press_maybe(
+                            state
+                                .fee_rate
+                                .fee_rate()
+                                .map(|_| Message::BasketReviewPress),
+                        )
+                        .padding([10, 20])
+                        .width(Shrink),
+                )
+                .align_x(Center)
+                .width(Fill),
+            ]
+            .spacing(10),
+        )
+        .padding(20)
+    });
+
     column![
         container(
             text_input("space", space_name)
@@ -226,6 +611,7 @@ pub fn view<'a>(
         .padding(20),
         center(main).padding(20),
     ]
+    .push_maybe(basket)
     .spacing(10)
     .into()
 }
@@ -0,0 +1,210 @@
+use iced::widget::{
+    button, center, column, container, horizontal_space, row, text, text_input, Column,
+};
+use iced::{Border, Center, Element, Fill, Font, Shrink, Theme};
+
+use crate::icon;
+use crate::store::{Amount, TxInfo, TxKind};
+use crate::unit::Unit;
+
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    label: String,
+    error: Option<String>,
+}
+
+impl State {
+    pub fn new(label: Option<&str>) -> Self {
+        Self {
+            label: label.unwrap_or_default().to_string(),
+            error: None,
+        }
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    BackPress,
+    TxidCopyPress { txid: String },
+    LabelInput(String),
+    LabelSavePress,
+}
+
+#[derive(Debug, Clone)]
+pub enum Task {
+    None,
+    Back,
+    WriteClipboard(String),
+    SetLabel(String),
+}
+
+pub fn update(state: &mut State, message: Message) -> Task {
+    state.error = None;
+    match message {
+        Message::BackPress => Task::Back,
+        Message::TxidCopyPress { txid } => Task::WriteClipboard(txid),
+        Message::LabelInput(label) => {
+            state.label = label;
+            Task::None
+        }
+        Message::LabelSavePress => Task::SetLabel(state.label.trim().to_string()),
+    }
+}
+
+pub fn view<'a>(
+    state: &'a State,
+    unit: Unit,
+    tip_height: u32,
+    transaction: Option<&'a TxInfo>,
+    saved_label: Option<&'a str>,
+) -> Element<'a, Message> {
+    let back = container(
+        button("Back")
+            .style(button::secondary)
+            .on_press(Message::BackPress)
+            .padding([10, 20])
+            .width(Shrink),
+    )
+    .align_x(Center)
+    .width(Fill);
+
+    let Some(transaction) = transaction else {
+        return center(
+            column![text("Transaction not found"), back]
+                .spacing(10)
+                .align_x(Center),
+        )
+        .into();
+    };
+
+    let txid = transaction.txid.to_string();
+    let (kind, _) = TxKind::classify(transaction);
+    let detail_row = |label: &'a str, value: String| {
+        row![text(label), horizontal_space(), text(value)]
+            .spacing(10)
+            .align_y(Center)
+    };
+    let net = if transaction.received >= transaction.sent {
+        format!("+{}", unit.format(transaction.received - transaction.sent))
+    } else {
+        format!("-{}", unit.format(transaction.sent - transaction.received))
+    };
+    let mut rows = vec![("Type", kind.to_string())];
+    rows.extend(TxKind::space_events(transaction).map(|(kind, space)| {
+        (
+            "Space operation",
+            match space {
+                Some(space) => format!("{} {}", kind, space),
+                None => kind.to_string(),
+            },
+        )
+    }));
+    rows.extend([
+        (
+            "Status",
+            match transaction.block_height.filter(|_| transaction.confirmed) {
+                Some(height) => format!(
+                    "{} confirmations",
+                    tip_height.saturating_sub(height).saturating_add(1)
+                ),
+                None => "Unconfirmed".into(),
+            },
+        ),
+        (
+            "Block height",
+            transaction
+                .block_height
+                .map_or_else(|| "None".into(), |height| height.to_string()),
+        ),
+        ("Sent", unit.format(transaction.sent)),
+        ("Received", unit.format(transaction.received)),
+        ("Net change", net),
+        (
+            "Fee",
+            transaction
+                .fee
+                .map_or_else(|| "Unknown".into(), |fee: Amount| unit.format(fee)),
+        ),
+    ]);
+
+    center(
+        Column::new()
+            .push_maybe(state.error.as_ref().map(|error| {
+                container(
+                    text(error)
+                        .style(|theme: &Theme| text::Style {
+                            color: Some(theme.extended_palette().danger.base.text),
+                        })
+                        .center()
+                        .width(Fill),
+                )
+                .style(|theme: &Theme| {
+                    container::Style::default()
+                        .background(theme.extended_palette().danger.base.color)
+                })
+                .width(Fill)
+                .padding([10, 30])
+            }))
+            .push(
+                row![
+                    text(txid.clone()).font(Font::MONOSPACE),
+                    horizontal_space(),
+                    button(text(icon::COPY).font(icon::FONT))
+                        .style(button::secondary)
+                        .on_press(Message::TxidCopyPress { txid }),
+                ]
+                .spacing(5)
+                .align_y(Center),
+            )
+            .push(
+                container(
+                    Column::with_children(
+                        rows.into_iter()
+                            .map(|(label, value)| detail_row(label, value).into()),
+                    )
+                    .spacing(10),
+                )
+                .padding(20)
+                .style(|theme: &Theme| {
+                    let palette = theme.extended_palette();
+                    container::Style::default().border(Border {
+                        color: palette.secondary.base.text,
+                        width: 1.0,
+                        radius: 5.0.into(),
+                    })
+                }),
+            )
+            .push(
+                column![
+                    text("Label"),
+                    row![
+                        text_input("optional", &state.label)
+                            .on_input(Message::LabelInput)
+                            .padding(10),
+                        button("Save label")
+                            .style(button::secondary)
+                            .on_press_maybe(
+                                (state.label.trim() != saved_label.unwrap_or_default())
+                                    .then_some(Message::LabelSavePress),
+                            )
+                            .padding(10),
+                    ]
+                    .spacing(5),
+                ]
+                .spacing(5),
+            )
+            .push(text(
+                "Block time, fee rate, inputs, outputs and raw hex are not provided by the \
+                 node's wallet transaction list",
+            ))
+            .push(back)
+            .spacing(10)
+            .max_width(800),
+    )
+    .padding(20)
+    .into()
+}
@@ -1,76 +1,451 @@
 use iced::widget::{
-    button, center, column, container, horizontal_space, row, scrollable, text, Column,
+    button, center, column, container, horizontal_space, mouse_area, row, scrollable, text,
+    text_input, Column, Row,
 };
 use iced::Alignment::Center;
-use iced::{Border, Element, Fill, Font, Theme};
+use iced::{mouse, Border, Element, Fill, Font, Theme};
 
 use crate::icon;
-use crate::store::TxInfo;
+use crate::labels::Labels;
+use crate::store::{FeeRate, TxInfo, TxKind, Txid};
+use crate::unit::Unit;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+
+    pub fn export(&self, transactions: &[TxInfo], labels: &Labels) -> String {
+        match self {
+            Self::Csv => {
+                let mut result =
+                    String::from("txid,type,space,sent,received,fee,confirmed,height,label\n");
+                for transaction in transactions {
+                    let (kind, space) = TxKind::classify(transaction);
+                    result.push_str(&format!(
+                        "{},{},{},{},{},{},{},{},{}\n",
+                        transaction.txid,
+                        kind,
+                        csv_field(space.unwrap_or_default()),
+                        transaction.sent.to_sat(),
+                        transaction.received.to_sat(),
+                        transaction
+                            .fee
+                            .map_or(String::new(), |fee| fee.to_sat().to_string()),
+                        transaction.confirmed,
+                        transaction
+                            .block_height
+                            .map_or(String::new(), |height| height.to_string()),
+                        csv_field(labels.transaction(&transaction.txid).unwrap_or_default()),
+                    ));
+                }
+                result
+            }
+            Self::Json => serde_json::to_string_pretty(
+                &transactions
+                    .iter()
+                    .map(|transaction| {
+                        let (kind, space) = TxKind::classify(transaction);
+                        serde_json::json!({
+                            "txid": transaction.txid.to_string(),
+                            "type": kind.to_string(),
+                            "space": space,
+                            "sent": transaction.sent.to_sat(),
+                            "received": transaction.received.to_sat(),
+                            "fee": transaction.fee.map(|fee| fee.to_sat()),
+                            "confirmed": transaction.confirmed,
+                            "height": transaction.block_height,
+                            "label": labels.transaction(&transaction.txid),
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .unwrap(),
+        }
+    }
+}
+
+// TxInfo has no vsize, assume a single input with payment and change outputs
+const ESTIMATED_TX_VBYTES: u64 = 155;
+
+fn current_fee_rate(transaction: &TxInfo) -> Option<u64> {
+    transaction
+        .fee
+        .map(|fee| fee.to_sat().div_ceil(ESTIMATED_TX_VBYTES))
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    bump_fee: Option<(Txid, Option<u64>, String)>,
+    filter: Option<TxKind>,
+    loading_more: bool,
+    exporting: bool,
+    export_path: Option<String>,
+    error: Option<String>,
+}
+
+impl State {
+    pub fn set_loaded_more(&mut self) {
+        self.loading_more = false
+    }
+
+    pub fn reset_bump_fee(&mut self) {
+        self.bump_fee = None
+    }
+
+    pub fn set_export_failed(&mut self) {
+        self.exporting = false
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.exporting = false;
+        self.error = Some(error)
+    }
+}
 
 #[derive(Debug, Clone)]
 pub enum Message {
     TxidCopyPress { txid: String },
+    TransactionPress { txid: Txid },
+    FilterSelect(Option<TxKind>),
+    BumpFeePress(Txid, Option<u64>),
+    FeeRateInput(String),
+    BumpFeeConfirmPress,
+    BumpFeeCancelPress,
+    LoadMorePress,
+    ListScroll(scrollable::Viewport),
+    ExportPress(ExportFormat),
+    ExportSaved(Option<Result<String, String>>),
+}
+
+#[derive(Debug, Clone)]
+pub enum Task {
+    None,
+    WriteClipboard(String),
+    ShowTransaction { txid: Txid },
+    BumpFee { txid: Txid, fee_rate: FeeRate },
+    LoadMore,
+    Export(ExportFormat),
 }
 
-pub fn view<'a>(transactions: &'a [TxInfo]) -> Element<'a, Message> {
+fn validate(fee_rate: &str, current_fee_rate: Option<u64>) -> Option<FeeRate> {
+    fee_rate
+        .parse()
+        .ok()
+        .filter(|sat_per_vb| *sat_per_vb > current_fee_rate.unwrap_or(0))
+        .and_then(FeeRate::from_sat_per_vb)
+}
+
+pub fn update(state: &mut State, message: Message) -> Task {
+    match message {
+        Message::TxidCopyPress { txid } => Task::WriteClipboard(txid),
+        Message::TransactionPress { txid } => Task::ShowTransaction { txid },
+        Message::FilterSelect(filter) => {
+            state.filter = filter;
+            Task::None
+        }
+        Message::BumpFeePress(txid, current_fee_rate) => {
+            state.bump_fee = Some((txid, current_fee_rate, String::new()));
+            Task::None
+        }
+        Message::FeeRateInput(fee_rate) => {
+            if let Some((_, _, ref mut input)) = state.bump_fee {
+                if fee_rate.chars().all(|c| c.is_ascii_digit()) {
+                    *input = fee_rate;
+                }
+            }
+            Task::None
+        }
+        Message::BumpFeeConfirmPress => {
+            state.error = None;
+            if let Some((txid, current_fee_rate, fee_rate)) = state.bump_fee.as_ref() {
+                if let Some(fee_rate) = validate(fee_rate, *current_fee_rate) {
+                    return Task::BumpFee {
+                        txid: *txid,
+                        fee_rate,
+                    };
+                }
+            }
+            Task::None
+        }
+        Message::BumpFeeCancelPress => {
+            state.bump_fee = None;
+            Task::None
+        }
+        Message::LoadMorePress => {
+            if state.loading_more {
+                Task::None
+            } else {
+                state.loading_more = true;
+                Task::LoadMore
+            }
+        }
+        Message::ListScroll(viewport) => {
+            if viewport.relative_offset().y >= 0.95 && !state.loading_more {
+                state.loading_more = true;
+                Task::LoadMore
+            } else {
+                Task::None
+            }
+        }
+        Message::ExportPress(format) => {
+            if state.exporting {
+                Task::None
+            } else {
+                state.exporting = true;
+                state.export_path = None;
+                state.error = None;
+                Task::Export(format)
+            }
+        }
+        Message::ExportSaved(result) => {
+            state.exporting = false;
+            state.error = None;
+            match result {
+                Some(Ok(path)) => state.export_path = Some(path),
+                Some(Err(e)) => state.error = Some(e),
+                None => {}
+            }
+            Task::None
+        }
+    }
+}
+
+pub fn view<'a>(
+    state: &'a State,
+    unit: Unit,
+    transactions: &'a [TxInfo],
+    has_more: bool,
+    labels: &'a Labels,
+) -> Element<'a, Message> {
     if transactions.is_empty() {
         center(text("No transactions yet")).into()
     } else {
-        scrollable(
+        let bump_fee_form = |current_fee_rate: Option<u64>, fee_rate: &'a String| {
+            Row::new()
+                .push_maybe(current_fee_rate.map(|current_fee_rate| {
+                    text(format!("Current about {} sat/vB", current_fee_rate))
+                }))
+                .push(text("New fee rate"))
+                .push(
+                    text_input("sat/vB", fee_rate)
+                        .on_input(Message::FeeRateInput)
+                        .padding(5)
+                        .width(150),
+                )
+                .push(
+                    button("Bump")
+                        .on_press_maybe(
+                            validate(fee_rate, current_fee_rate)
+                                .map(|_| Message::BumpFeeConfirmPress),
+                        )
+                        .padding([5, 10]),
+                )
+                .push(
+                    button("Cancel")
+                        .style(button::secondary)
+                        .on_press(Message::BumpFeeCancelPress)
+                        .padding([5, 10]),
+                )
+                .spacing(5)
+                .align_y(Center)
+        };
+
+        let mut transactions_list = scrollable(
             container(
-                Column::with_children(transactions.into_iter().map(|transaction| {
+                Column::with_children(transactions.iter().filter_map(|transaction| {
+                    let (kind, space) = TxKind::classify(transaction);
+                    let more_events = TxKind::space_events(transaction).count().saturating_sub(1);
+                    if state.filter.is_some_and(|filter| {
+                        filter != kind
+                            && TxKind::space_events(transaction).all(|(kind, _)| kind != filter)
+                    }) {
+                        return None;
+                    }
                     let txid = transaction.txid.to_string();
-                    container(column![
-                        row![
-                            text(if transaction.sent >= transaction.received {
-                                icon::ARROW_DOWN_FROM_ARC
-                            } else {
-                                icon::ARROW_DOWN_TO_ARC
-                            })
-                            .font(icon::FONT),
-                            text(txid.clone()).font(Font::MONOSPACE),
-                            horizontal_space(),
-                            button(text(icon::COPY).font(icon::FONT))
+                    let bump_fee = if transaction.confirmed {
+                        None
+                    } else {
+                        Some(match state.bump_fee.as_ref() {
+                            Some((txid, current_fee_rate, fee_rate))
+                                if *txid == transaction.txid =>
+                            {
+                                bump_fee_form(*current_fee_rate, fee_rate)
+                            }
+                            _ => row![button("Bump fee")
                                 .style(button::secondary)
-                                .on_press(Message::TxidCopyPress { txid })
-                        ]
-                        .spacing(5)
-                        .align_y(Center),
-                        row![
-                            text(format!("Sent: {} SAT", transaction.sent.to_sat())),
-                            text(format!("Received: {} SAT", transaction.received.to_sat())),
-                        ]
-                        .push_maybe(
-                            transaction
-                                .fee
-                                .map(|fee| { text(format!("Fee: {} SAT", fee.to_sat())) })
-                        )
-                        .spacing(5),
-                    ])
-                    .style(|theme: &Theme| {
-                        let palette = theme.extended_palette();
-                        container::Style::default()
-                            .border(Border {
-                                color: palette.secondary.base.text,
-                                width: 1.0,
-                                radius: 5.0.into(),
-                            })
-                            .background(if transaction.confirmed {
-                                palette.background.strong.color
-                            } else {
-                                palette.background.weak.color
+                                .on_press(Message::BumpFeePress(
+                                    transaction.txid,
+                                    current_fee_rate(transaction)
+                                ))
+                                .padding([5, 10])],
+                        })
+                    };
+                    Some(
+                        mouse_area(
+                            container(
+                                column![
+                                    row![
+                                        text(if transaction.sent > transaction.received {
+                                            icon::ARROW_DOWN_FROM_ARC
+                                        } else {
+                                            icon::ARROW_DOWN_TO_ARC
+                                        })
+                                        .font(icon::FONT),
+                                        text(kind.to_string()),
+                                    ]
+                                    .push_maybe(space.map(text))
+                                    .push_maybe(
+                                        (more_events > 0)
+                                            .then(|| { text(format!("and {} more", more_events)) })
+                                    )
+                                    .push(text(txid.clone()).font(Font::MONOSPACE))
+                                    .push(horizontal_space())
+                                    .push(
+                                        button(text(icon::COPY).font(icon::FONT))
+                                            .style(button::secondary)
+                                            .on_press(Message::TxidCopyPress { txid }),
+                                    )
+                                    .spacing(5)
+                                    .align_y(Center),
+                                    row![
+                                        text(format!("Sent: {}", unit.format(transaction.sent))),
+                                        text(format!(
+                                            "Received: {}",
+                                            unit.format(transaction.received)
+                                        )),
+                                    ]
+                                    .push_maybe(
+                                        transaction.fee.map(|fee| {
+                                            text(format!("Fee: {}", unit.format(fee)))
+                                        })
+                                    )
+                                    .spacing(5),
+                                ]
+                                .push_maybe(labels.transaction(&transaction.txid).map(text))
+                                .push_maybe(bump_fee)
+                                .spacing(5),
+                            )
+                            .style(|theme: &Theme| {
+                                let palette = theme.extended_palette();
+                                container::Style::default()
+                                    .border(Border {
+                                        color: palette.secondary.base.text,
+                                        width: 1.0,
+                                        radius: 5.0.into(),
+                                    })
+                                    .background(if transaction.confirmed {
+                                        palette.background.strong.color
+                                    } else {
+                                        palette.background.weak.color
+                                    })
                             })
-                    })
-                    .padding(10)
+                            .padding(10)
+                            .width(Fill),
+                        )
+                        .on_press(Message::TransactionPress {
+                            txid: transaction.txid,
+                        })
+                        .interaction(mouse::Interaction::Pointer)
+                        .into(),
+                    )
+                }))
+                .push_maybe(has_more.then(|| {
+                    container(
+                        button(if state.loading_more {
+                            "Loading"
+                        } else {
+                            "Load more"
+                        })
+                        .style(button::secondary)
+                        .on_press_maybe((!state.loading_more).then_some(Message::LoadMorePress))
+                        .padding([5, 10]),
+                    )
+                    .align_x(Center)
                     .width(Fill)
-                    .into()
                 }))
                 .spacing(5),
             )
             .padding(10),
         )
         .spacing(2)
-        .height(Fill)
-        .into()
+        .height(Fill);
+        if has_more {
+            transactions_list = transactions_list.on_scroll(Message::ListScroll);
+        }
+
+        let filter_chip = |label: String, filter: Option<TxKind>| {
+            button(text(label))
+                .style(if state.filter == filter {
+                    button::primary
+                } else {
+                    button::secondary
+                })
+                .on_press(Message::FilterSelect(filter))
+                .padding([5, 10])
+        };
+        let filters = row![filter_chip("All".into(), None)]
+            .extend(
+                TxKind::ALL
+                    .into_iter()
+                    .map(|kind| filter_chip(kind.to_string(), Some(kind)).into()),
+            )
+            .push(horizontal_space())
+            .extend([ExportFormat::Csv, ExportFormat::Json].map(|format| {
+                button(text(format!(
+                    "Export {}",
+                    format.extension().to_uppercase()
+                )))
+                .style(button::secondary)
+                .on_press_maybe((!state.exporting).then_some(Message::ExportPress(format)))
+                .padding([5, 10])
+                .into()
+            }))
+            .spacing(5)
+            .padding([10, 10]);
+
+        Column::new()
+            .push_maybe(state.error.as_ref().map(|error| {
+                container(
+                    text(error)
+                        .style(|theme: &Theme| text::Style {
+                            color: Some(theme.extended_palette().danger.base.text),
+                        })
+                        .center()
+                        .width(Fill),
+                )
+                .style(|theme: &Theme| {
+                    container::Style::default()
+                        .background(theme.extended_palette().danger.base.color)
+                })
+                .width(Fill)
+                .padding([10, 30])
+            }))
+            .push(filters)
+            .push_maybe(
+                state
+                    .export_path
+                    .as_ref()
+                    .map(|path| text(format!("Exported to {}", path)).width(Fill).center()),
+            )
+            .push(transactions_list)
+            .into()
     }
 }
@@ -1,11 +1,22 @@
+use std::fmt;
+use std::path::PathBuf;
+
 use iced::widget::qr_code::Data as QrCode;
 use rustc_hash::FxHashMap;
 use spaced::wallets;
 
+use crate::labels::Labels;
+
 pub use protocol::{slabel::SLabel, Covenant, FullSpaceOut};
-pub use wallet::bitcoin::{Amount, Denomination};
+pub use wallet::bitcoin::{
+    address::NetworkUnchecked, Amount, Denomination, FeeRate, Network, OutPoint, Txid,
+};
+pub use wallet::export::WalletExport;
+use wallet::tx_event::TxEventKind;
 pub use wallets::{AddressKind, Balance, TxInfo, WalletOutput};
 
+pub type CoinAddress = wallet::bitcoin::Address<NetworkUnchecked>;
+
 #[derive(Debug)]
 pub struct Address {
     pub text: String,
@@ -17,6 +28,11 @@ impl Address {
         let qr_code = QrCode::new(&text).unwrap();
         Self { text, qr_code }
     }
+
+    pub fn try_new(text: String) -> Result<Self, String> {
+        let qr_code = QrCode::new(&text).map_err(|e| e.to_string())?;
+        Ok(Self { text, qr_code })
+    }
 }
 
 #[derive(Default, Debug)]
@@ -27,6 +43,75 @@ pub struct Wallet {
     pub balance: Amount,
     pub spaces: Vec<SLabel>,
     pub transactions: Vec<TxInfo>,
+    pub has_more_transactions: bool,
+    pub labels: Labels,
+}
+
+pub const TRANSACTIONS_PAGE_SIZE: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxKind {
+    Send,
+    Receive,
+    Open,
+    Bid,
+    Register,
+    Transfer,
+    Renew,
+}
+
+impl TxKind {
+    pub const ALL: [Self; 7] = [
+        Self::Send,
+        Self::Receive,
+        Self::Open,
+        Self::Bid,
+        Self::Register,
+        Self::Transfer,
+        Self::Renew,
+    ];
+
+    pub fn space_events(transaction: &TxInfo) -> impl Iterator<Item = (Self, Option<&str>)> {
+        transaction.events.iter().filter_map(|event| {
+            let kind = match event.kind {
+                TxEventKind::Open => Self::Open,
+                TxEventKind::Bid => Self::Bid,
+                TxEventKind::Register => Self::Register,
+                TxEventKind::Transfer => Self::Transfer,
+                TxEventKind::Renew => Self::Renew,
+                _ => return None,
+            };
+            Some((kind, event.space.as_deref()))
+        })
+    }
+
+    pub fn classify(transaction: &TxInfo) -> (Self, Option<&str>) {
+        Self::space_events(transaction).next().unwrap_or(
+            if transaction.sent > transaction.received {
+                (Self::Send, None)
+            } else {
+                (Self::Receive, None)
+            },
+        )
+    }
+}
+
+impl fmt::Display for TxKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Send => "Send",
+                Self::Receive => "Receive",
+                Self::Open => "Open",
+                Self::Bid => "Bid",
+                Self::Register => "Register",
+                Self::Transfer => "Transfer",
+                Self::Renew => "Renew",
+            }
+        )
+    }
 }
 
 impl Wallet {
@@ -36,26 +121,90 @@ impl Wallet {
             ..Default::default()
         }
     }
+
+    pub fn add_transactions(&mut self, skip: usize, transactions: Vec<TxInfo>) {
+        let is_full_page = transactions.len() == TRANSACTIONS_PAGE_SIZE;
+        if skip == 0 {
+            let covered = transactions
+                .last()
+                .filter(|_| is_full_page)
+                .and_then(|last| self.transactions.iter().position(|tx| tx.txid == last.txid))
+                .map_or(self.transactions.len(), |index| index + 1);
+            let older: Vec<TxInfo> = self
+                .transactions
+                .split_off(covered)
+                .into_iter()
+                .filter(|old| transactions.iter().all(|tx| tx.txid != old.txid))
+                .collect();
+            self.has_more_transactions =
+                is_full_page && (older.is_empty() || self.has_more_transactions);
+            self.transactions = transactions;
+            self.transactions.extend(older);
+        } else {
+            self.has_more_transactions = is_full_page;
+            for transaction in transactions {
+                if self
+                    .transactions
+                    .iter()
+                    .all(|tx| tx.txid != transaction.txid)
+                {
+                    self.transactions.push(transaction);
+                }
+            }
+        }
+    }
 }
 
 #[derive(Default, Debug)]
 pub struct Store {
     pub tip_height: u32,
-    pub wallet: Option<Wallet>,
+    pub wallet_names: Vec<String>,
+    pub wallets: FxHashMap<String, Wallet>,
     pub spaces: FxHashMap<SLabel, Option<Covenant>>,
+    pub space_outpoints: FxHashMap<SLabel, OutPoint>,
+    current_wallet: Option<String>,
+    labels_dir: Option<PathBuf>,
 }
 
 impl Store {
+    pub fn new(labels_dir: Option<PathBuf>) -> Self {
+        Self {
+            labels_dir,
+            ..Default::default()
+        }
+    }
+
+    pub fn set_wallet(&mut self, name: String) {
+        let labels_dir = self.labels_dir.as_ref();
+        self.wallets.entry(name.clone()).or_insert_with(|| {
+            let mut wallet = Wallet::new(name.clone());
+            if let Some(labels_dir) = labels_dir {
+                wallet.labels = Labels::load(labels_dir.join(format!("{}.json", name)));
+            }
+            wallet
+        });
+        if !self.wallet_names.contains(&name) {
+            self.wallet_names.push(name.clone());
+        }
+        self.current_wallet = Some(name);
+    }
+
+    pub fn get_wallet(&self) -> Option<&Wallet> {
+        self.current_wallet
+            .as_ref()
+            .and_then(|name| self.wallets.get(name))
+    }
+
     pub fn get_wallet_name(&self) -> Option<String> {
-        self.wallet.as_ref().map(|wallet| wallet.name.clone())
+        self.current_wallet.clone()
     }
 
     pub fn get_wallet_with_name(&mut self, name: &str) -> Option<&mut Wallet> {
-        self.wallet.as_mut().filter(|wallet| wallet.name == name)
+        self.wallets.get_mut(name)
     }
 
     pub fn get_wallet_spaces(&self) -> Option<impl Iterator<Item = (&SLabel, &Covenant)>> {
-        self.wallet.as_ref().map(|wallet| {
+        self.get_wallet().map(|wallet| {
             wallet
                 .spaces
                 .iter()
@@ -0,0 +1,152 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::store::{Amount, Denomination};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Unit {
+    #[default]
+    Sat,
+    MilliBtc,
+    Btc,
+}
+
+impl Unit {
+    pub const ALL: [Unit; 3] = [Unit::Sat, Unit::MilliBtc, Unit::Btc];
+
+    fn denomination(&self) -> Denomination {
+        match self {
+            Unit::Sat => Denomination::Satoshi,
+            Unit::MilliBtc => Denomination::MilliBitcoin,
+            Unit::Btc => Denomination::Bitcoin,
+        }
+    }
+
+    pub fn is_valid_input(&self, input: &str) -> bool {
+        let (integer, fraction) = input.split_once('.').unwrap_or((input, ""));
+        (*self != Unit::Sat || !input.contains('.'))
+            && fraction.chars().all(|c| c.is_ascii_digit())
+            && is_grouped(integer, input.contains('.'))
+    }
+
+    pub fn to_input(&self, amount: Amount) -> String {
+        amount.to_string_in(self.denomination())
+    }
+
+    pub fn parse(&self, input: &str) -> Option<Amount> {
+        let (integer, fraction) = input.split_once('.').unwrap_or((input, ""));
+        if fraction.contains(',') || !is_grouped(integer, true) {
+            return None;
+        }
+        let input: String = input.chars().filter(|c| *c != ',').collect();
+        Amount::from_str_in(&input, self.denomination()).ok()
+    }
+
+    pub fn convert_input(&self, input: &str, unit: Unit) -> String {
+        self.parse(input)
+            .map(|amount| unit.to_input(amount))
+            .unwrap_or_default()
+    }
+
+    pub fn format(&self, amount: Amount) -> String {
+        let value = amount.to_string_in(self.denomination());
+        let (integer, fraction) = match value.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (value.as_str(), None),
+        };
+        let mut grouped = String::new();
+        for (i, c) in integer.chars().enumerate() {
+            if i != 0 && (integer.len() - i) % 3 == 0 {
+                grouped.push(',');
+            }
+            grouped.push(c);
+        }
+        match fraction {
+            Some(fraction) => format!("{}.{} {}", grouped, fraction, self),
+            None => format!("{} {}", grouped, self),
+        }
+    }
+}
+
+// Commas are only accepted as thousands separators in the integer part, the last group may be
+// incomplete while the value is still being typed
+fn is_grouped(integer: &str, complete: bool) -> bool {
+    let mut groups = integer.split(',');
+    let first = groups.next().unwrap_or_default();
+    if !first.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+    if !integer.contains(',') {
+        return true;
+    }
+    if first.is_empty() || first.len() > 3 || first.starts_with('0') {
+        return false;
+    }
+    let groups: Vec<&str> = groups.collect();
+    groups.iter().enumerate().all(|(i, group)| {
+        group.chars().all(|c| c.is_ascii_digit())
+            && (group.len() == 3 || (!complete && i == groups.len() - 1 && group.len() < 3))
+    })
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Unit::Sat => "SAT",
+                Unit::MilliBtc => "mBTC",
+                Unit::Btc => "BTC",
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_thousands_separators() {
+        assert_eq!(Unit::Sat.parse("1,000"), Some(Amount::from_sat(1_000)));
+        assert_eq!(
+            Unit::Sat.parse("12,345,678"),
+            Some(Amount::from_sat(12_345_678))
+        );
+        assert_eq!(
+            Unit::Btc.parse("1,000.5"),
+            Some(Amount::from_sat(100_050_000_000))
+        );
+        assert_eq!(Unit::Btc.parse("0.5"), Some(Amount::from_sat(50_000_000)));
+        assert_eq!(Unit::Btc.parse("0,5"), None);
+        assert_eq!(Unit::Btc.parse("0,500"), None);
+        assert_eq!(Unit::Sat.parse("1,00"), None);
+        assert_eq!(Unit::Sat.parse("1000,000"), None);
+        assert_eq!(Unit::Sat.parse(",100"), None);
+        assert_eq!(Unit::Sat.parse("1,,000"), None);
+        assert_eq!(Unit::Btc.parse("1.000,5"), None);
+    }
+
+    #[test]
+    fn valid_input_while_typing() {
+        assert!(Unit::Sat.is_valid_input(""));
+        assert!(Unit::Sat.is_valid_input("1,"));
+        assert!(Unit::Sat.is_valid_input("1,00"));
+        assert!(Unit::Sat.is_valid_input("1,000,0"));
+        assert!(Unit::Btc.is_valid_input("1,000."));
+        assert!(!Unit::Sat.is_valid_input("1.5"));
+        assert!(!Unit::Btc.is_valid_input("1,00.5"));
+        assert!(!Unit::Btc.is_valid_input("0,5"));
+        assert!(!Unit::Btc.is_valid_input("0.5,"));
+        assert!(!Unit::Sat.is_valid_input("1,0000"));
+    }
+
+    #[test]
+    fn convert_input() {
+        assert_eq!(Unit::Sat.convert_input("100,000", Unit::Btc), "0.001");
+        assert_eq!(Unit::Btc.convert_input("0.001", Unit::Sat), "100000");
+        assert_eq!(Unit::Sat.convert_input("", Unit::Btc), "");
+    }
+}
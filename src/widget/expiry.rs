@@ -0,0 +1,33 @@
+use iced::widget::text;
+use iced::{Element, Theme};
+
+const BLOCKS_PER_DAY: u32 = 144;
+const EXPIRY_WARNING_BLOCKS: u32 = 30 * BLOCKS_PER_DAY;
+
+fn time_left(blocks: u32) -> String {
+    if blocks >= BLOCKS_PER_DAY {
+        format!("~{} days", blocks / BLOCKS_PER_DAY)
+    } else {
+        format!("~{} hours", blocks * 10 / 60)
+    }
+}
+
+pub fn view<'a, Message: 'a>(tip_height: u32, expire_height: u32) -> Element<'a, Message> {
+    let label = if tip_height == 0 {
+        format!("Expires at block {}", expire_height)
+    } else if expire_height > tip_height {
+        format!(
+            "Expires at block {} ({} left)",
+            expire_height,
+            time_left(expire_height - tip_height)
+        )
+    } else {
+        format!("Expired at block {}", expire_height)
+    };
+    let is_expiring = tip_height != 0 && expire_height <= tip_height + EXPIRY_WARNING_BLOCKS;
+    text(label)
+        .style(move |theme: &Theme| text::Style {
+            color: is_expiring.then(|| theme.extended_palette().danger.base.color),
+        })
+        .into()
+}
@@ -0,0 +1,108 @@
+use std::fmt;
+
+use iced::widget::{pick_list, row, text, text_input};
+use iced::{Center, Element};
+
+use crate::store::FeeRate;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Preset {
+    #[default]
+    Auto,
+    Low,
+    Medium,
+    High,
+    Custom,
+}
+
+impl Preset {
+    const ALL: [Preset; 5] = [
+        Preset::Auto,
+        Preset::Low,
+        Preset::Medium,
+        Preset::High,
+        Preset::Custom,
+    ];
+
+    fn sat_per_vb(&self) -> Option<u64> {
+        match self {
+            Preset::Low => Some(1),
+            Preset::Medium => Some(5),
+            Preset::High => Some(20),
+            Preset::Auto | Preset::Custom => None,
+        }
+    }
+}
+
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Preset::Auto => write!(f, "Auto"),
+            Preset::Custom => write!(f, "Custom"),
+            preset => write!(f, "{} sat/vB (fixed)", preset.sat_per_vb().unwrap()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    preset: Preset,
+    custom: String,
+}
+
+impl State {
+    /// Returns `None` if the custom fee rate is invalid and `Some(None)` if the node should estimate it
+    pub fn fee_rate(&self) -> Option<Option<FeeRate>> {
+        match self.preset {
+            Preset::Auto => Some(None),
+            Preset::Custom => self
+                .custom
+                .parse()
+                .ok()
+                .filter(|sat_per_vb| *sat_per_vb > 0)
+                .and_then(FeeRate::from_sat_per_vb)
+                .map(Some),
+            preset => Some(FeeRate::from_sat_per_vb(preset.sat_per_vb().unwrap())),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    PresetSelect(Preset),
+    CustomInput(String),
+}
+
+pub fn update(state: &mut State, message: Message) {
+    match message {
+        Message::PresetSelect(preset) => state.preset = preset,
+        Message::CustomInput(custom) => {
+            if custom.chars().all(|c| c.is_ascii_digit()) {
+                state.custom = custom
+            }
+        }
+    }
+}
+
+pub fn view<'a>(state: &'a State) -> Element<'a, Message> {
+    row![
+        text("Fee rate"),
+        pick_list(Preset::ALL, Some(state.preset), Message::PresetSelect).padding(10),
+    ]
+    .push_maybe((state.preset == Preset::Custom).then(|| {
+        text_input("sat/vB", &state.custom)
+            .on_input(Message::CustomInput)
+            .padding(10)
+            .width(150)
+    }))
+    .spacing(10)
+    .align_y(Center)
+    .into()
+}
+
+pub fn label(fee_rate: Option<FeeRate>) -> String {
+    match fee_rate {
+        Some(fee_rate) => format!("{} sat/vB", fee_rate.to_sat_per_vb_ceil()),
+        None => "Estimated by the node".into(),
+    }
+}
@@ -0,0 +1,3 @@
+pub mod expiry;
+pub mod fee_rate;
+pub mod review;
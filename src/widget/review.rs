@@ -0,0 +1,51 @@
+use iced::widget::{button, column, container, row, text, Column};
+use iced::{Border, Center, Element, Fill, Font, Shrink, Theme};
+
+pub fn view<'a, Message: Clone + 'a>(
+    title: String,
+    rows: Vec<(&'a str, String)>,
+    on_confirm: Message,
+    on_cancel: Message,
+) -> Element<'a, Message> {
+    column![
+        text(title).size(20),
+        container(
+            Column::with_children(rows.into_iter().map(|(label, value)| {
+                row![
+                    text(label).width(150),
+                    text(value).font(Font::MONOSPACE).width(Fill),
+                ]
+                .spacing(10)
+                .into()
+            }))
+            .spacing(5),
+        )
+        .padding(10)
+        .style(|theme: &Theme| {
+            let palette = theme.extended_palette();
+            container::Style::default().border(Border {
+                color: palette.secondary.base.text,
+                width: 1.0,
+                radius: 0.into(),
+            })
+        }),
+        container(
+            row![
+                button("Cancel")
+                    .style(button::secondary)
+                    .on_press(on_cancel)
+                    .padding([10, 20])
+                    .width(Shrink),
+                button("Confirm")
+                    .on_press(on_confirm)
+                    .padding([10, 20])
+                    .width(Shrink),
+            ]
+            .spacing(10),
+        )
+        .align_x(Center)
+        .width(Fill),
+    ]
+    .spacing(10)
+    .into()
+}